@@ -0,0 +1,207 @@
+// Copyright © 2023 Analog Devices, Inc. All Rights Reserved. This software is
+// proprietary to Analog Devices, Inc. and its licensors.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Array of sensors read concurrently as aligned multi-channel frames.
+
+use core::cell::Cell;
+
+use embassy_futures::join::join_array;
+use micromath::vector::I32x3;
+
+pub use adi_adxl355::array::{ArrayError, ArrayReadOutcome, SensorFlags};
+
+use crate::{delay_long_us, Adxl355, Error, Protocol};
+use crate::{Config, ExternalSync, FlushMethod, HealthPolicy, HealthReport, Variant};
+use embedded_hal_async::delay::DelayNs;
+
+/// Array of `N` ADXL355 sensors, typically on separate chip selects, configured and read
+/// together. The sensors are accessed concurrently and the fifos are read into frames holding
+/// one sample per sensor.
+///
+/// The sensors are started as close together as the bus allows, they sample at the same
+/// instants when they share an external clock and sync, see [`Self::set_external_sync`].
+pub struct Adxl355Array<PROTOCOL: Protocol, VARIANT: Variant, const N: usize> {
+    sensors: [Adxl355<PROTOCOL, VARIANT>; N],
+}
+
+impl<PROTOCOL, VARIANT, const N: usize> Adxl355Array<PROTOCOL, VARIANT, N>
+where
+    PROTOCOL: Protocol,
+    VARIANT: Variant,
+{
+    /// Create an array from the drivers of its sensors.
+    pub fn new(sensors: [Adxl355<PROTOCOL, VARIANT>; N]) -> Self {
+        Adxl355Array { sensors }
+    }
+
+    /// Get the drivers of the sensors.
+    pub fn sensors(&mut self) -> &mut [Adxl355<PROTOCOL, VARIANT>; N] {
+        &mut self.sensors
+    }
+
+    /// Release the drivers of the sensors.
+    pub fn into_inner(self) -> [Adxl355<PROTOCOL, VARIANT>; N] {
+        self.sensors
+    }
+
+    /// Apply the same configuration to every sensor.
+    pub async fn configure(
+        &mut self,
+        config: Config,
+    ) -> Result<(), ArrayError<PROTOCOL::ProtocolError>> {
+        join_array(
+            enumerate_mut(&mut self.sensors).map(|(sensor, acc)| async move {
+                acc.configure(config)
+                    .await
+                    .map_err(|error| ArrayError { sensor, error })
+            }),
+        )
+        .await
+        .into_iter()
+        .collect()
+    }
+
+    /// Apply the same external synchronization to every sensor, to have them sample at the
+    /// same instants.
+    pub async fn set_external_sync(
+        &mut self,
+        sync: ExternalSync,
+    ) -> Result<(), ArrayError<PROTOCOL::ProtocolError>> {
+        join_array(
+            enumerate_mut(&mut self.sensors).map(|(sensor, acc)| async move {
+                acc.set_external_sync(sync)
+                    .await
+                    .map_err(|error| ArrayError { sensor, error })
+            }),
+        )
+        .await
+        .into_iter()
+        .collect()
+    }
+
+    /// Set every sensor in measurement mode concurrently, wait for the filters of all of them
+    /// to settle for at most `max_settling_us`, then empty their fifos concurrently, so that
+    /// they hold aligned samples. Returns the settling time left in microseconds, see
+    /// [`Adxl355::start_measurement_and_settle`].
+    pub async fn start(
+        &mut self,
+        delay: &mut impl DelayNs,
+        max_settling_us: u64,
+    ) -> Result<u64, ArrayError<PROTOCOL::ProtocolError>> {
+        let settling_times_us = join_array(enumerate_mut(&mut self.sensors).map(
+            |(sensor, acc)| async move {
+                acc.settling_time_us()
+                    .await
+                    .map_err(|error| ArrayError { sensor, error })
+            },
+        ))
+        .await;
+        let mut settling_time_us = 0;
+        for sensor_settling_us in settling_times_us {
+            settling_time_us = settling_time_us.max(sensor_settling_us?);
+        }
+
+        join_array(
+            enumerate_mut(&mut self.sensors).map(|(sensor, acc)| async move {
+                acc.set_measurement_mode()
+                    .await
+                    .map_err(|error| ArrayError { sensor, error })
+            }),
+        )
+        .await
+        .into_iter()
+        .collect::<Result<(), _>>()?;
+        let wait_us = settling_time_us.min(max_settling_us);
+        delay_long_us(delay, wait_us).await;
+        join_array(
+            enumerate_mut(&mut self.sensors).map(|(sensor, acc)| async move {
+                acc.flush_fifo(FlushMethod::Drain)
+                    .await
+                    .map(|_| ())
+                    .map_err(|error| ArrayError { sensor, error })
+            }),
+        )
+        .await
+        .into_iter()
+        .collect::<Result<(), _>>()?;
+        Ok(settling_time_us - wait_us)
+    }
+
+    /// Set every sensor in standby mode.
+    pub async fn stop(&mut self) -> Result<(), ArrayError<PROTOCOL::ProtocolError>> {
+        join_array(
+            enumerate_mut(&mut self.sensors).map(|(sensor, acc)| async move {
+                acc.set_standby_mode()
+                    .await
+                    .map_err(|error| ArrayError { sensor, error })
+            }),
+        )
+        .await
+        .into_iter()
+        .collect()
+    }
+
+    /// Read the fifos into `frames`, the same number of samples from each sensor.
+    /// A sensor failing is flagged in the returned [`ArrayReadOutcome`] along with its error,
+    /// and the others are still read.
+    pub async fn read_frames(
+        &mut self,
+        frames: &mut [[I32x3; N]],
+    ) -> ArrayReadOutcome<PROTOCOL::ProtocolError, N> {
+        let mut fifo_entries = join_array(
+            enumerate_mut(&mut self.sensors).map(|(_, acc)| acc.get_nb_samples_in_fifo()),
+        )
+        .await;
+        let samples = ArrayReadOutcome::aligned_samples(&fifo_entries, frames.len());
+
+        let cells = Cell::from_mut(&mut *frames).as_slice_of_cells();
+        let reads = join_array(enumerate_mut(&mut self.sensors).map(|(sensor, acc)| {
+            let entries = core::mem::replace(&mut fifo_entries[sensor], Ok(0));
+            async move {
+                entries?;
+                let store = |index: usize, sample| {
+                    let mut frame = cells[index].get();
+                    frame[sensor] = sample;
+                    cells[index].set(frame);
+                };
+                acc.read_fifo_with_outcome(samples, store).await
+            }
+        }))
+        .await;
+
+        let outcome = ArrayReadOutcome::from_fifo_reads(reads);
+        for (sensor, flags) in outcome.sensors.iter().enumerate() {
+            if flags.failed {
+                for frame in frames[..outcome.frames].iter_mut() {
+                    frame[sensor] = I32x3::default();
+                }
+            }
+        }
+        outcome
+    }
+
+    /// Check the health of every sensor concurrently, see [`Adxl355::health_check`].
+    pub async fn health_check(
+        &mut self,
+        policy: HealthPolicy,
+    ) -> [Result<HealthReport, Error<PROTOCOL::ProtocolError>>; N] {
+        join_array(enumerate_mut(&mut self.sensors).map(|(_, acc)| acc.health_check(policy))).await
+    }
+}
+
+/// Borrow each item of `array` along with its index.
+fn enumerate_mut<T, const N: usize>(array: &mut [T; N]) -> [(usize, &mut T); N] {
+    let mut items = array.iter_mut().enumerate();
+    core::array::from_fn(|_| items.next().expect("array holds N items"))
+}
@@ -0,0 +1,55 @@
+// Copyright © 2023 Analog Devices, Inc. All Rights Reserved. This software is
+// proprietary to Analog Devices, Inc. and its licensors.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use embedded_hal::i2c;
+use embedded_hal_async::i2c::I2c;
+
+pub use adi_adxl355::discovery::{is_device_absent, DiscoveredDevice, I2cDiscovery};
+
+use crate::{DeviceI2cAddress, DeviceInfo, Error, Register, Variant};
+
+/// Probe both ASEL addresses on the I2C bus and return the sensors of the given [`Variant`] found.
+///
+/// An address which is not acknowledged is considered empty, a device answering with other
+/// identification registers than the expected ones is ignored. The bus is only borrowed, it
+/// can be used to create the drivers afterwards.
+pub async fn discover_i2c<I2C, VARIANT>(
+    i2c: &mut I2C,
+    _variant: VARIANT,
+) -> Result<I2cDiscovery, Error<I2C::Error>>
+where
+    I2C: I2c,
+    VARIANT: Variant,
+{
+    let mut discovery = I2cDiscovery::default();
+
+    for (slot, address) in discovery.devices.iter_mut().zip(DeviceI2cAddress::ALL) {
+        let mut buf = [0u8; 4];
+        match i2c
+            .write_read(address as u8, &[Register::DEVID_AD.addr()], &mut buf)
+            .await
+        {
+            Ok(()) => {
+                let info = DeviceInfo::from_registers(buf);
+                if info.check::<VARIANT>().is_ok() {
+                    *slot = Some(DiscoveredDevice { address, info });
+                }
+            }
+            Err(e) if is_device_absent(i2c::Error::kind(&e)) => {}
+            Err(e) => return Err(Error::Protocol(e)),
+        }
+    }
+
+    Ok(discovery)
+}
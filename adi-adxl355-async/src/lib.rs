@@ -16,39 +16,78 @@
 #![allow(async_fn_in_trait)]
 #![doc = include_str!("../README.md")]
 
+#[cfg(feature = "array")]
+pub mod array;
+pub mod discovery;
+pub mod retry;
+pub mod trace;
+
+pub use adi_adxl355::acquisition::*;
+pub use adi_adxl355::budget::*;
 pub use adi_adxl355::config::*;
+pub use adi_adxl355::dump::*;
+pub use adi_adxl355::fifo::*;
+pub use adi_adxl355::health::*;
+pub use adi_adxl355::identity::*;
 pub use adi_adxl355::interrupt::*;
 pub use adi_adxl355::modes::*;
+pub use adi_adxl355::planner::*;
 pub use adi_adxl355::register::*;
+pub use adi_adxl355::regs;
+use adi_adxl355::regs::{Filter, RegisterBits, RegisterValue, SelfTest, WritableRegister};
+pub use adi_adxl355::sample::*;
+use adi_adxl355::sans_io::{self, RegisterOp, ResetAction};
 pub use adi_adxl355::status::*;
+pub use adi_adxl355::sync::*;
+#[cfg(feature = "units")]
+pub use adi_adxl355::units::*;
+pub use adi_adxl355::variant::*;
 pub use adi_adxl355::DeviceI2cAddress;
+pub use adi_adxl355::Error;
 pub use adi_adxl355::DEVICE_ID;
+#[cfg(feature = "array")]
+pub use array::*;
+use core::future::{poll_fn, Future};
+use core::marker::PhantomData;
+use core::pin::pin;
+use core::task::Poll;
+pub use discovery::*;
+use embedded_hal::i2c;
 use embedded_hal::spi;
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::digital::Wait;
 use embedded_hal_async::i2c::I2c;
 use embedded_hal_async::spi::SpiDevice;
 use micromath::vector::{F32x3, I32x3};
+pub use retry::*;
+pub use trace::*;
 
 /// ADXL355 async driver.
-pub struct Adxl355<PROTOCOL: Protocol> {
+/// The `VARIANT` parameter selects the member of the ADXL35x family, see [`Variant`].
+pub struct Adxl355<PROTOCOL: Protocol, VARIANT: Variant = Adxl355Variant> {
     pub protocol: PROTOCOL,
+    info: DeviceInfo,
     shadow_values: [u8; 5],
-    range_scale_factor: f32,
+    range: Range,
+    written: ControlImage,
+    samples: StuckSampleDetector,
+    external_sync: ExternalSync,
+    variant: PhantomData<VARIANT>,
 }
 
+/// ADXL357 async driver.
+pub type Adxl357<PROTOCOL> = Adxl355<PROTOCOL, Adxl357Variant>;
+
+/// ADXL359 async driver.
+pub type Adxl359<PROTOCOL> = Adxl355<PROTOCOL, Adxl359Variant>;
+
 impl<SPI> Adxl355<SpiProtocol<SPI>>
 where
     SPI: SpiDevice,
 {
     /// Create a new ADXL355 driver from given SPI peripheral in unknown state.
     pub async fn new_spi(spi: SPI) -> Result<Self, Error<SPI::Error>> {
-        let mut adxl355 = Adxl355 {
-            protocol: SpiProtocol { spi },
-            shadow_values: [0; 5],
-            range_scale_factor: 0.0,
-        };
-        adxl355.init_shadow_values().await?;
-        adxl355.range_scale_factor = adxl355.get_range().await?.scale_factor();
-        Ok(adxl355)
+        Self::new_spi_with_variant(spi, Adxl355Variant).await
     }
 
     /// Create a new ADXL355 driver from given SPI peripheral in the given config.
@@ -60,23 +99,27 @@ where
     }
 }
 
+impl<SPI, VARIANT> Adxl355<SpiProtocol<SPI>, VARIANT>
+where
+    SPI: SpiDevice,
+    VARIANT: Variant,
+{
+    /// Create a new driver for the given [`Variant`] from given SPI peripheral in unknown state.
+    pub async fn new_spi_with_variant(
+        spi: SPI,
+        variant: VARIANT,
+    ) -> Result<Self, Error<SPI::Error>> {
+        Self::new_with_protocol(SpiProtocol::new(spi), variant).await
+    }
+}
+
 impl<I2C> Adxl355<I2cProtocol<I2C>>
 where
     I2C: I2c,
 {
     /// Create a new ADXL355 driver from given I2C peripheral in unknown state.
     pub async fn new_i2c(i2c: I2C, address: DeviceI2cAddress) -> Result<Self, Error<I2C::Error>> {
-        let mut adxl355 = Adxl355 {
-            protocol: I2cProtocol {
-                i2c,
-                address: address as u8,
-            },
-            shadow_values: [0; 5],
-            range_scale_factor: 0.0,
-        };
-        adxl355.init_shadow_values().await?;
-        adxl355.range_scale_factor = adxl355.get_range().await?.scale_factor();
-        Ok(adxl355)
+        Self::new_i2c_with_variant(i2c, address, Adxl355Variant).await
     }
 
     /// Create a new ADXL355 driver from given I2C peripheral in the given config.
@@ -92,38 +135,64 @@ where
     }
 }
 
-impl<PROTOCOL> Adxl355<PROTOCOL>
+impl<I2C, VARIANT> Adxl355<I2cProtocol<I2C>, VARIANT>
+where
+    I2C: I2c,
+    VARIANT: Variant,
+{
+    /// Create a new driver for the given [`Variant`] from given I2C peripheral in unknown state.
+    pub async fn new_i2c_with_variant(
+        i2c: I2C,
+        address: DeviceI2cAddress,
+        variant: VARIANT,
+    ) -> Result<Self, Error<I2C::Error>> {
+        Self::new_with_protocol(I2cProtocol::new(i2c, address), variant).await
+    }
+}
+
+impl<PROTOCOL, VARIANT> Adxl355<PROTOCOL, VARIANT>
 where
     PROTOCOL: Protocol,
+    VARIANT: Variant,
 {
+    /// Create a new driver for the given [`Variant`] over any [`Protocol`] implementation,
+    /// such as a [`RetryingProtocol`] wrapping a [`SpiProtocol`] or an [`I2cProtocol`].
+    pub async fn new_with_protocol(
+        protocol: PROTOCOL,
+        _variant: VARIANT,
+    ) -> Result<Self, Error<PROTOCOL::ProtocolError>> {
+        let mut adxl355 = Adxl355 {
+            protocol,
+            info: DeviceInfo::from_registers([0; 4]),
+            shadow_values: [0; 5],
+            range: Range::default(),
+            written: ControlImage::unknown(),
+            samples: StuckSampleDetector::default(),
+            external_sync: ExternalSync::default(),
+            variant: PhantomData,
+        };
+        adxl355.init().await?;
+        Ok(adxl355)
+    }
+
     /// Configure the ADXL355.
     /// The ADXL355 is reset before the config is applied to make sure it is in standby mode.
+    /// The range must be one of the [`Variant::RANGES`].
     pub async fn configure(
         &mut self,
         config: Config,
     ) -> Result<(), Error<PROTOCOL::ProtocolError>> {
-        let dev_id = self.get_device_id().await?;
-        if dev_id != DEVICE_ID {
-            return Err(Error::BadDeviceId(dev_id));
-        }
+        sans_io::validate_config::<VARIANT, _>(&config)?;
 
         self.reset().await?;
 
         let mode = self.get_mode().await?;
+        sans_io::check_standby(mode)?;
 
-        if !mode.is_in_standby() {
-            return Err(Error::DeviceRunning);
+        for op in sans_io::config_ops(&config) {
+            self.apply(op).await?;
         }
-
-        self.protocol
-            .write_register(Register::FILTER, (config.hpf.val() << 4) | config.odr.val())
-            .await?;
-        let mut range_register_value = self.protocol.read_register(Register::RANGE).await?;
-        range_register_value |= config.range.val();
-        self.protocol
-            .write_register(Register::RANGE, range_register_value)
-            .await?;
-        self.range_scale_factor = config.range.scale_factor();
+        self.range = config.range;
 
         Ok(())
     }
@@ -134,7 +203,43 @@ where
         Ok(Status(status_val))
     }
 
-    /// Get the device ID, `0xED` is expected.
+    /// Wait for a new sample by polling the data ready status every
+    /// [`sans_io::DATA_READY_POLL_INTERVAL_US`]. Returns [`Error::Timeout`] when no sample is
+    /// ready after `timeout_us`, also the case when the device is in standby mode.
+    pub async fn wait_data_ready(
+        &mut self,
+        delay: &mut impl DelayNs,
+        timeout_us: u32,
+    ) -> Result<(), Error<PROTOCOL::ProtocolError>> {
+        let mut waited_us = 0;
+        while !self.get_status().await?.is_data_ready() {
+            if waited_us >= timeout_us {
+                return Err(Error::Timeout);
+            }
+            delay.delay_us(sans_io::DATA_READY_POLL_INTERVAL_US).await;
+            waited_us = waited_us.saturating_add(sans_io::DATA_READY_POLL_INTERVAL_US);
+        }
+        Ok(())
+    }
+
+    /// Wait for a new sample on the DRDY pin, active high. Returns [`Error::Timeout`] when no
+    /// sample is ready after `timeout_us`, also the case when the device is in standby mode.
+    /// With external synchronization DRDY is the sync input, use [`Self::wait_data_ready`].
+    pub async fn wait_data_ready_pin(
+        &mut self,
+        drdy: &mut impl Wait,
+        delay: &mut impl DelayNs,
+        timeout_us: u32,
+    ) -> Result<(), Error<PROTOCOL::ProtocolError>> {
+        wait_pin(drdy, true, delay, timeout_us).await
+    }
+
+    /// Get the [`DeviceInfo`] read and validated when the driver was created.
+    pub fn device_info(&self) -> DeviceInfo {
+        self.info
+    }
+
+    /// Get the device ID, [`Variant::PART_ID`] is expected.
     pub async fn get_device_id(&mut self) -> Result<u8, Error<PROTOCOL::ProtocolError>> {
         self.protocol.read_register(Register::PARTID).await
     }
@@ -145,28 +250,25 @@ where
     }
 
     /// Reset the device and make sure the NVM is loaded correctly.
+    /// The NVM busy flag is polled [`sans_io::SoftReset::MAX_POLLS`] times back to back, the time
+    /// allowed for the NVM to load depends on the bus speed, prefer [`Self::reset_with_delay`].
     pub async fn reset(&mut self) -> Result<(), Error<PROTOCOL::ProtocolError>> {
-        self.protocol.write_register(Register::RESET, 0x52).await?;
-
-        let mut nb_of_retries = 255;
-        let mut nvm_busy = self.get_status().await?.is_nvm_busy();
-
-        while nvm_busy && nb_of_retries > 0 {
-            nvm_busy = self.get_status().await?.is_nvm_busy();
-            nb_of_retries -= 1;
-        }
-
-        if nb_of_retries == 0 || nvm_busy {
-            return Err(Error::SoftResetFailed);
-        }
-
-        let shadow_values = self.get_shadow_values().await?;
+        self.run_reset(sans_io::SoftReset::new(self.shadow_values), &mut NoDelay)
+            .await
+    }
 
-        if self.shadow_values == shadow_values {
-            Ok(())
-        } else {
-            Err(Error::SoftResetFailed)
-        }
+    /// Reset the device and make sure the NVM is loaded correctly, polling the NVM busy flag
+    /// every [`sans_io::SoftReset::POLL_INTERVAL_US`] for up to
+    /// [`sans_io::SoftReset::DEFAULT_TIMEOUT_US`].
+    pub async fn reset_with_delay(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), Error<PROTOCOL::ProtocolError>> {
+        let reset = sans_io::SoftReset::with_timeout(
+            self.shadow_values,
+            sans_io::SoftReset::DEFAULT_TIMEOUT_US,
+        );
+        self.run_reset(reset, delay).await
     }
 
     /// Get the current [`Mode`] of the device.
@@ -179,17 +281,14 @@ where
 
     /// Set the [`Mode`] of the device.
     pub async fn set_mode(&mut self, mode: Mode) -> Result<(), Error<PROTOCOL::ProtocolError>> {
-        self.protocol
-            .write_register(Register::POWER_CTL, mode.0)
-            .await
+        self.write_tracked(Register::POWER_CTL, mode.0).await
     }
 
-    /// Get the current [`Range`] of the device.
+    /// Get the current [`Range`] of the device, [`Error::UnsupportedRange`] when the range bits
+    /// hold the reserved value.
     pub async fn get_range(&mut self) -> Result<Range, Error<PROTOCOL::ProtocolError>> {
-        self.protocol
-            .read_register(Register::RANGE)
-            .await
-            .map(Range::from)
+        let value = self.protocol.read_register(Register::RANGE).await?;
+        VARIANT::range_from_register(value).ok_or(Error::UnsupportedRange)
     }
 
     /// Get the current [`Odr`] of the device.
@@ -219,83 +318,228 @@ where
         Ok(Config { range, odr, hpf })
     }
 
+    /// Read every register but FIFO_DATA in 3 bursts, for diagnostics.
+    pub async fn dump_registers(&mut self) -> Result<RegisterDump, Error<PROTOCOL::ProtocolError>> {
+        let status_and_data = self
+            .protocol
+            .read_block(RegisterBlock::STATUS_AND_DATA)
+            .await?;
+        let control = self.protocol.read_block(RegisterBlock::CONTROL).await?;
+        let shadow = self.protocol.read_block(RegisterBlock::SHADOW).await?;
+        Ok(RegisterDump::new::<VARIANT>(
+            status_and_data,
+            control,
+            shadow,
+        ))
+    }
+
+    /// Check the device against the state the driver expects, see [`HealthReport`].
+    /// Only the identity, control and shadow registers are read, the data ready flag is kept.
+    /// The control registers are written back when [`HealthReport::should_reapply`].
+    pub async fn health_check(
+        &mut self,
+        policy: HealthPolicy,
+    ) -> Result<HealthReport, Error<PROTOCOL::ProtocolError>> {
+        let registers = HealthRegisters {
+            identity: self.protocol.read_block(RegisterBlock::IDENTITY).await?,
+            control: self.protocol.read_block(RegisterBlock::CONTROL).await?,
+            shadow: self.protocol.read_block(RegisterBlock::SHADOW).await?,
+        };
+        let mut report = HealthReport::evaluate(
+            &registers,
+            &self.written,
+            &self.info,
+            self.shadow_values,
+            self.samples.identical(),
+            &policy,
+        );
+
+        if report.should_reapply(&policy) {
+            self.reapply_control_image().await?;
+            report.reapplied = true;
+        }
+        Ok(report)
+    }
+
+    /// Get the values the driver last wrote to the control registers.
+    pub fn control_image(&self) -> ControlImage {
+        self.written
+    }
+
+    /// Get the [`PowerState`] of the device.
+    pub async fn get_power_state(&mut self) -> Result<PowerState, Error<PROTOCOL::ProtocolError>> {
+        self.get_mode().await.map(PowerState::from)
+    }
+
     /// Set device in measurement mode.
     pub async fn set_measurement_mode(&mut self) -> Result<(), Error<PROTOCOL::ProtocolError>> {
-        let mode = self.protocol.read_register(Register::POWER_CTL).await?;
-        self.protocol
-            .write_register(Register::POWER_CTL, mode & !Mode::STANDBY_BIT)
-            .await
+        self.update_power_ctl(Mode::STANDBY_BIT, false).await
+    }
+
+    /// Set device in measurement mode and wait for the low-pass and high-pass filters to settle,
+    /// see [`Config::settling_time_us`], for at most `max_settling_us`. The samples stored in the
+    /// fifo in the meantime are discarded.
+    ///
+    /// Returns the settling time left in microseconds, 0 when the next sample read is settled.
+    /// With the lowest high-pass corners the filters take minutes, or hours at low ODRs, to
+    /// settle: a `max_settling_us` of 0 only reports the settling time.
+    pub async fn start_measurement_and_settle(
+        &mut self,
+        delay: &mut impl DelayNs,
+        max_settling_us: u64,
+    ) -> Result<u64, Error<PROTOCOL::ProtocolError>> {
+        let settling_time_us = self.settling_time_us().await?;
+        let wait_us = settling_time_us.min(max_settling_us);
+        self.set_measurement_mode().await?;
+        delay_long_us(delay, wait_us).await;
+        self.discard_fifo().await?;
+        Ok(settling_time_us - wait_us)
     }
 
     /// Set device in standby mode.
     pub async fn set_standby_mode(&mut self) -> Result<(), Error<PROTOCOL::ProtocolError>> {
-        let mode = self.protocol.read_register(Register::POWER_CTL).await?;
-        self.protocol
-            .write_register(Register::POWER_CTL, mode | Mode::STANDBY_BIT)
-            .await
+        self.update_power_ctl(Mode::STANDBY_BIT, true).await
     }
 
-    /// Get raw temperature value.
-    pub async fn get_temperature_raw(&mut self) -> Result<u16, Error<PROTOCOL::ProtocolError>> {
-        let mut buf: [u8; 2] = [0; 2];
+    /// Enable or disable temperature processing, other POWER_CTL bits are left untouched.
+    pub async fn set_temperature_enabled(
+        &mut self,
+        enabled: bool,
+    ) -> Result<(), Error<PROTOCOL::ProtocolError>> {
+        self.update_power_ctl(Mode::TEMP_OFF_BIT, !enabled).await
+    }
 
-        self.protocol
-            .read_multiple_registers(Register::TEMP2, &mut buf)
+    /// Enable or force to 0 the DRDY output, other POWER_CTL bits are left untouched.
+    pub async fn set_drdy_output_enabled(
+        &mut self,
+        enabled: bool,
+    ) -> Result<(), Error<PROTOCOL::ProtocolError>> {
+        self.update_power_ctl(Mode::DRDY_OFF_BIT, !enabled).await
+    }
+
+    /// Put the device in standby mode, run `op` and restore the previous [`Mode`].
+    /// The whole POWER_CTL value is restored, including the TEMP_OFF and DRDY_OFF bits,
+    /// whether `op` succeeds or not. If `op` fails, its error is returned once the mode is
+    /// restored.
+    ///
+    /// Entering standby resets the FIFO, the samples it held are reported as lost in the
+    /// returned [`StandbyReport`].
+    pub async fn with_standby<OP>(
+        &mut self,
+        op: OP,
+    ) -> Result<StandbyReport<OP::Output>, Error<PROTOCOL::ProtocolError>>
+    where
+        OP: StandbyOp<PROTOCOL, VARIANT>,
+    {
+        let previous_mode = self.get_mode().await?;
+        let lost_samples = if previous_mode.is_in_standby() {
+            0
+        } else {
+            sans_io::complete_samples(self.get_nb_samples_in_fifo().await?)
+        };
+
+        self.set_mode(Mode(previous_mode.0 | Mode::STANDBY_BIT))
             .await?;
+        let result = op.run(self).await;
+        let restored = self.set_mode(previous_mode).await;
+
+        let output = result?;
+        restored?;
 
-        Ok((((buf[0] & 0x0F) as u16) << 8) | ((buf[1] as u16) & 0xFF))
+        Ok(StandbyReport {
+            output,
+            previous_mode,
+            lost_samples,
+        })
     }
 
-    // Get temperature in Celsius.
+    /// Get raw temperature value.
+    pub async fn get_temperature_raw(&mut self) -> Result<u16, Error<PROTOCOL::ProtocolError>> {
+        let buf = self.protocol.read_block(RegisterBlock::TEMPERATURE).await?;
+
+        Ok(sans_io::decode_temperature_raw(&buf))
+    }
+
+    /// Get temperature in Celsius.
+    /// Returns [`Error::TemperatureDisabled`] if temperature processing is turned off,
+    /// the temperature registers would only hold stale data.
     pub async fn get_temparature(&mut self) -> Result<f32, Error<PROTOCOL::ProtocolError>> {
+        sans_io::check_temperature_enabled(self.get_mode().await?)?;
+
         let raw_temp = self.get_temperature_raw().await?;
-        Ok(((((raw_temp as i32) - 1885i32) as f32) / (-9.05f32)) + 25.0f32)
+        Ok(sans_io::temperature_celsius(raw_temp))
     }
 
-    /// Get raw acceleration values.
-    pub async fn get_raw_accel_sample(&mut self) -> Result<I32x3, Error<PROTOCOL::ProtocolError>> {
-        let mut buf: [u8; 9] = [0; 9];
-        self.protocol
-            .read_multiple_registers(Register::XDATA3, &mut buf)
-            .await?;
+    /// Get temperature as [`Celsius`], see [`Self::get_temparature`].
+    #[cfg(feature = "units")]
+    pub async fn get_temperature(&mut self) -> Result<Celsius, Error<PROTOCOL::ProtocolError>> {
+        self.get_temparature().await.map(Celsius)
+    }
 
-        let mut sample = I32x3 { x: 0, y: 0, z: 0 };
+    /// Get temperature in milli-degrees Celsius, with integer arithmetic only.
+    /// Returns [`Error::TemperatureDisabled`] if temperature processing is turned off.
+    pub async fn get_temperature_milli_celsius(
+        &mut self,
+    ) -> Result<i32, Error<PROTOCOL::ProtocolError>> {
+        sans_io::check_temperature_enabled(self.get_mode().await?)?;
 
-        Self::acc_buf_to_raw_sample(&buf, &mut sample);
+        let raw_temp = self.get_temperature_raw().await?;
+        Ok(sans_io::temperature_milli_celsius(raw_temp))
+    }
 
+    /// Get raw acceleration values.
+    pub async fn get_raw_accel_sample(&mut self) -> Result<I32x3, Error<PROTOCOL::ProtocolError>> {
+        let buf = self.protocol.read_block(RegisterBlock::ACCEL_DATA).await?;
+        let sample = sans_io::decode_sample(&buf);
+        self.samples.update(sample);
         Ok(sample)
     }
 
     /// Get acceleration values in g.
     pub async fn get_accel_sample(&mut self) -> Result<F32x3, Error<PROTOCOL::ProtocolError>> {
-        let mut buf: [u8; 9] = [0; 9];
-        self.protocol
-            .read_multiple_registers(Register::XDATA3, &mut buf)
-            .await?;
+        let raw = self.get_raw_accel_sample().await?;
+        Ok(sans_io::scale_sample(raw, self.range.scale_factor()))
+    }
 
-        let mut sample = F32x3 {
-            x: 0.0,
-            y: 0.0,
-            z: 0.0,
-        };
+    /// Get acceleration values in the unit `U`, e.g. `get_acceleration::<G>()`.
+    #[cfg(feature = "units")]
+    pub async fn get_acceleration<U: AccelerationUnit>(
+        &mut self,
+    ) -> Result<Acceleration<U>, Error<PROTOCOL::ProtocolError>> {
+        self.get_accel_sample().await.map(Acceleration::from_g)
+    }
 
-        Self::acc_buf_to_sample(&buf, &mut sample, self.range_scale_factor);
+    /// Get acceleration values in micro-g, with integer arithmetic only.
+    pub async fn get_accel_sample_micro_g(
+        &mut self,
+    ) -> Result<I32x3, Error<PROTOCOL::ProtocolError>> {
+        let raw = self.get_raw_accel_sample().await?;
+        Ok(sans_io::scale_sample_ratio(
+            raw,
+            self.range.micro_g_per_lsb(),
+        ))
+    }
 
-        Ok(sample)
+    /// Get acceleration values in milli-m/s², with integer arithmetic only.
+    pub async fn get_accel_sample_milli_mps2(
+        &mut self,
+    ) -> Result<I32x3, Error<PROTOCOL::ProtocolError>> {
+        let raw = self.get_raw_accel_sample().await?;
+        Ok(sans_io::scale_sample_ratio(
+            raw,
+            self.range.milli_mps2_per_lsb(),
+        ))
     }
 
     /// Enable device self test feature.
     pub async fn start_self_test(&mut self) -> Result<(), Error<PROTOCOL::ProtocolError>> {
-        self.protocol
-            .write_register(Register::SELF_TEST, 0x03)
+        self.write_reg(*SelfTest::default().set_st1(true).set_st2(true))
             .await
     }
 
     /// Disable device self test feature.
     pub async fn stop_self_test(&mut self) -> Result<(), Error<PROTOCOL::ProtocolError>> {
-        self.protocol
-            .write_register(Register::SELF_TEST, 0x00)
-            .await
+        self.write_reg(SelfTest::default()).await
     }
 
     /// Apply offset trims for raw axis data after all other signal processing.
@@ -306,22 +550,17 @@ where
         offset_y: i16,
         offset_z: i16,
     ) -> Result<(), Error<PROTOCOL::ProtocolError>> {
-        let mut buf = [
-            Register::OFFSET_X_H.addr(),
-            ((offset_x >> 8) & 0xFF) as u8,
-            ((offset_x) & 0xFF) as u8,
-            ((offset_y >> 8) & 0xFF) as u8,
-            ((offset_y) & 0xFF) as u8,
-            ((offset_z >> 8) & 0xFF) as u8,
-            ((offset_z) & 0xFF) as u8,
-        ];
-        self.protocol.write_multiple_registers(&mut buf).await
+        self.write_tracked_block(
+            RegisterBlock::OFFSETS.start(),
+            &sans_io::encode_offsets(offset_x, offset_y, offset_z),
+        )
+        .await
     }
 
     /// Get the number of data samples stored in the FIFO
     pub async fn get_nb_samples_in_fifo(&mut self) -> Result<u8, Error<PROTOCOL::ProtocolError>> {
-        let nb_samples = self.protocol.read_register(Register::FIFO_ENTRIES).await? & 0x7F;
-        Ok(nb_samples)
+        let fifo_entries = self.protocol.read_register(Register::FIFO_ENTRIES).await?;
+        Ok(sans_io::decode_fifo_entries(fifo_entries))
     }
 
     /// Set the maximum number of samples the fifo will store.
@@ -330,12 +569,9 @@ where
         &mut self,
         fifo_samples: u8,
     ) -> Result<(), Error<PROTOCOL::ProtocolError>> {
-        if !(1..=96).contains(&fifo_samples) {
-            return Err(Error::InvalidMaxNbFifoSamples);
-        }
+        sans_io::validate_fifo_samples(fifo_samples)?;
 
-        self.protocol
-            .write_register(Register::FIFO_SAMPLES, fifo_samples)
+        self.write_tracked(Register::FIFO_SAMPLES, fifo_samples)
             .await
     }
 
@@ -345,50 +581,9 @@ where
         &mut self,
         data: &mut [I32x3],
     ) -> Result<usize, Error<PROTOCOL::ProtocolError>> {
-        let mut nb_available_samples = self.get_nb_samples_in_fifo().await?;
-        if nb_available_samples < 3 {
-            return Err(Error::NotEnoughData);
-        }
-        let data_len = data.len();
-        let mut nb_samples_retrieved: usize = 0;
-
-        let mut buf = [0u8; 9];
-        self.protocol
-            .read_multiple_registers(Register::FIFO_DATA, &mut buf[..3])
-            .await?;
-        nb_available_samples -= 1;
-
-        // Make sure first value is from X axis
-        while buf[2] & 0x01 == 0 {
-            if nb_available_samples < 3 {
-                return Err(Error::NotEnoughData);
-            }
-
-            self.protocol
-                .read_multiple_registers(Register::FIFO_DATA, &mut buf[..3])
-                .await?;
-            nb_available_samples -= 1;
-        }
-
-        self.protocol
-            .read_multiple_registers(Register::FIFO_DATA, &mut buf[3..])
-            .await?;
-        nb_available_samples -= 2;
-
-        Self::acc_buf_to_raw_sample(&buf, &mut data[0]);
-        nb_samples_retrieved += 1;
-
-        while nb_available_samples >= 3 && data_len > nb_samples_retrieved {
-            self.protocol
-                .read_multiple_registers(Register::FIFO_DATA, &mut buf)
-                .await?;
-            nb_available_samples -= 3;
-
-            Self::acc_buf_to_raw_sample(&buf, &mut data[nb_samples_retrieved]);
-            nb_samples_retrieved += 1;
-        }
-
-        Ok(nb_samples_retrieved)
+        let capacity = data.len();
+        self.read_fifo(capacity, |index, sample| data[index] = sample)
+            .await
     }
 
     /// Get data from the fifo into provided array of accelerometer samples in g.
@@ -397,54 +592,168 @@ where
         &mut self,
         data: &mut [F32x3],
     ) -> Result<usize, Error<PROTOCOL::ProtocolError>> {
-        let mut nb_available_samples = self.get_nb_samples_in_fifo().await?;
-        if nb_available_samples < 3 {
-            return Err(Error::NotEnoughData);
-        }
-        let data_len = data.len();
-        let mut nb_samples_retrieved: usize = 0;
+        let capacity = data.len();
+        let scale_factor = self.range.scale_factor();
+        self.read_fifo(capacity, |index, sample| {
+            data[index] = sans_io::scale_sample(sample, scale_factor)
+        })
+        .await
+    }
 
-        let mut buf = [0u8; 9];
-        self.protocol
-            .read_multiple_registers(Register::FIFO_DATA, &mut buf[..3])
-            .await?;
-        nb_available_samples -= 1;
+    /// Get data from the fifo into provided array of accelerometer samples in the unit `U`.
+    /// The return value indicates how many samples were retrieved.
+    #[cfg(feature = "units")]
+    pub async fn get_fifo_acceleration<U: AccelerationUnit>(
+        &mut self,
+        data: &mut [Acceleration<U>],
+    ) -> Result<usize, Error<PROTOCOL::ProtocolError>> {
+        let capacity = data.len();
+        let scale_factor = self.range.scale_factor();
+        self.read_fifo(capacity, |index, sample| {
+            data[index] = Acceleration::from_g(sans_io::scale_sample(sample, scale_factor))
+        })
+        .await
+    }
 
-        // Make sure first value is from X axis
-        while buf[2] & 0x01 == 0 {
-            if nb_available_samples < 3 {
-                return Err(Error::NotEnoughData);
-            }
+    /// Get data from the fifo into provided array of accelerometer samples in micro-g, with
+    /// integer arithmetic only. The return value indicates how many samples were retrieved.
+    pub async fn get_fifo_data_micro_g(
+        &mut self,
+        data: &mut [I32x3],
+    ) -> Result<usize, Error<PROTOCOL::ProtocolError>> {
+        let capacity = data.len();
+        let ratio = self.range.micro_g_per_lsb();
+        self.read_fifo(capacity, |index, sample| {
+            data[index] = sans_io::scale_sample_ratio(sample, ratio)
+        })
+        .await
+    }
 
-            self.protocol
-                .read_multiple_registers(Register::FIFO_DATA, &mut buf[..3])
-                .await?;
-            nb_available_samples -= 1;
+    /// Get data from the fifo like [`Self::get_fifo_raw_data`], reporting in the returned
+    /// [`FifoReadOutcome`] whether the fifo overran and the entries dropped to resynchronize on
+    /// complete samples. A fifo holding less than one sample is not an error.
+    pub async fn get_fifo_raw_data_with_outcome(
+        &mut self,
+        data: &mut [I32x3],
+    ) -> Result<FifoReadOutcome, Error<PROTOCOL::ProtocolError>> {
+        let capacity = data.len();
+        self.read_fifo_with_outcome(capacity, |index, sample| data[index] = sample)
+            .await
+    }
+
+    /// Get data from the fifo like [`Self::get_fifo_data`], reporting the data lost in a
+    /// [`FifoReadOutcome`], see [`Self::get_fifo_raw_data_with_outcome`].
+    pub async fn get_fifo_data_with_outcome(
+        &mut self,
+        data: &mut [F32x3],
+    ) -> Result<FifoReadOutcome, Error<PROTOCOL::ProtocolError>> {
+        let capacity = data.len();
+        let scale_factor = self.range.scale_factor();
+        self.read_fifo_with_outcome(capacity, |index, sample| {
+            data[index] = sans_io::scale_sample(sample, scale_factor)
+        })
+        .await
+    }
+
+    /// Get data from the fifo like [`Self::get_fifo_acceleration`], reporting the data lost in
+    /// a [`FifoReadOutcome`], see [`Self::get_fifo_raw_data_with_outcome`].
+    #[cfg(feature = "units")]
+    pub async fn get_fifo_acceleration_with_outcome<U: AccelerationUnit>(
+        &mut self,
+        data: &mut [Acceleration<U>],
+    ) -> Result<FifoReadOutcome, Error<PROTOCOL::ProtocolError>> {
+        let capacity = data.len();
+        let scale_factor = self.range.scale_factor();
+        self.read_fifo_with_outcome(capacity, |index, sample| {
+            data[index] = Acceleration::from_g(sans_io::scale_sample(sample, scale_factor))
+        })
+        .await
+    }
+
+    /// Empty the fifo with the given [`FlushMethod`], returns the number of entries discarded.
+    /// The next fifo read starts on a complete sample.
+    pub async fn flush_fifo(
+        &mut self,
+        method: FlushMethod,
+    ) -> Result<u8, Error<PROTOCOL::ProtocolError>> {
+        match method {
+            FlushMethod::Drain => self.discard_fifo().await,
+            FlushMethod::StandbyToggle => {
+                let mode = self.get_mode().await?;
+                if mode.is_in_standby() {
+                    // The fifo is held in reset in standby mode.
+                    return Ok(0);
+                }
+                let fifo_entries = self.get_nb_samples_in_fifo().await?;
+                self.set_mode(Mode(mode.0 | Mode::STANDBY_BIT)).await?;
+                self.set_mode(mode).await?;
+                Ok(fifo_entries)
+            }
         }
+    }
 
-        self.protocol
-            .read_multiple_registers(Register::FIFO_DATA, &mut buf[3..])
+    /// Start a continuous acquisition: set the fifo watermark from the [`AcquisitionConfig`], map
+    /// the FIFO_FULL interrupt on INT1 in addition to the interrupts already mapped, then enter
+    /// measurement mode and wait for the filters to settle for at most
+    /// [`AcquisitionConfig::max_settling_us`], see [`Self::start_measurement_and_settle`].
+    /// Returns the settling time left in microseconds.
+    pub async fn start_acquisition(
+        &mut self,
+        acquisition: &mut Acquisition,
+        delay: &mut impl DelayNs,
+    ) -> Result<u64, Error<PROTOCOL::ProtocolError>> {
+        self.set_standby_mode().await?;
+        self.set_nb_max_samples_in_fifo(acquisition.config().fifo_samples())
             .await?;
-        nb_available_samples -= 2;
-
-        Self::acc_buf_to_sample(&buf, &mut data[0], self.range_scale_factor);
-        nb_samples_retrieved += 1;
+        self.modify_reg::<InterruptConfig, _>(|conf| {
+            conf.with_fifo_full_on_int1();
+        })
+        .await?;
+        let polarity = self.read_reg::<regs::Range>().await?.interrupt_polarity();
+
+        acquisition.start(polarity);
+        let max_settling_us = acquisition.config().max_settling_us;
+        self.start_measurement_and_settle(delay, max_settling_us.into())
+            .await
+    }
 
-        while nb_available_samples >= 3 && data_len > nb_samples_retrieved {
-            self.protocol
-                .read_multiple_registers(Register::FIFO_DATA, &mut buf)
+    /// Fill `buffer` with raw samples from the fifo, reading it each time the FIFO_FULL interrupt
+    /// is signaled on `int1`. Returns [`Error::Timeout`] when the interrupt is not signaled within
+    /// [`AcquisitionConfig::timeout_us`]. Overruns and dropped entries are counted in the
+    /// [`AcquisitionStats`].
+    pub async fn fill_buffer(
+        &mut self,
+        acquisition: &mut Acquisition,
+        buffer: &mut [I32x3],
+        int1: &mut impl Wait,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), Error<PROTOCOL::ProtocolError>> {
+        let active_high = acquisition.interrupt_polarity() == InterruptPolarity::ActiveHigh;
+        let timeout_us = acquisition.config().timeout_us;
+
+        let mut filled = 0;
+        let mut idle_us = 0;
+        while filled < buffer.len() {
+            wait_pin(int1, active_high, delay, timeout_us).await?;
+            let outcome = self
+                .get_fifo_raw_data_with_outcome(&mut buffer[filled..])
                 .await?;
-            nb_available_samples -= 3;
-
-            Self::acc_buf_to_sample(
-                &buf,
-                &mut data[nb_samples_retrieved],
-                self.range_scale_factor,
-            );
-            nb_samples_retrieved += 1;
+            acquisition.record(&outcome);
+            filled += outcome.samples;
+
+            // An interrupt with no sample to read, the INT1 polarity may be wrong.
+            if outcome.samples == 0 {
+                if idle_us >= timeout_us {
+                    return Err(Error::Timeout);
+                }
+                delay.delay_us(sans_io::DATA_READY_POLL_INTERVAL_US).await;
+                idle_us = idle_us.saturating_add(sans_io::DATA_READY_POLL_INTERVAL_US);
+            } else {
+                idle_us = 0;
+            }
         }
-
-        Ok(nb_samples_retrieved)
+        acquisition.buffer_filled();
+        Ok(())
     }
 
     /// Enable activity detection for selected axes
@@ -454,18 +763,8 @@ where
         y: bool,
         z: bool,
     ) -> Result<(), Error<PROTOCOL::ProtocolError>> {
-        let mut act_en = 0;
-        if x {
-            act_en += 0x01;
-        }
-        if y {
-            act_en += 0x02;
-        }
-        if z {
-            act_en += 0x04;
-        }
-
-        self.protocol.write_register(Register::ACT_EN, act_en).await
+        self.write_tracked(Register::ACT_EN, sans_io::encode_activity_enable(x, y, z))
+            .await
     }
 
     /// Set threshold for activity detection. The acceleration magnitude must be greater
@@ -476,13 +775,11 @@ where
         &mut self,
         threshold: u16,
     ) -> Result<(), Error<PROTOCOL::ProtocolError>> {
-        self.protocol
-            .write_multiple_registers(&mut [
-                Register::ACT_THRESH_H.addr(),
-                ((threshold >> 8) & 0xFF) as u8,
-                (threshold & 0xFF) as u8,
-            ])
-            .await
+        self.write_tracked_block(
+            RegisterBlock::ACTIVITY_THRESHOLD.start(),
+            &sans_io::encode_u16(threshold),
+        )
+        .await
     }
 
     /// Set number of consecutive events above threshold required to detect activity.
@@ -490,9 +787,7 @@ where
         &mut self,
         count: u8,
     ) -> Result<(), Error<PROTOCOL::ProtocolError>> {
-        self.protocol
-            .write_register(Register::ACT_COUNT, count)
-            .await
+        self.write_tracked(Register::ACT_COUNT, count).await
     }
 
     /// Configure interrupt pins INT1 and INT2.
@@ -500,9 +795,7 @@ where
         &mut self,
         conf: InterruptConfig,
     ) -> Result<(), Error<PROTOCOL::ProtocolError>> {
-        self.protocol
-            .write_register(Register::INT_MAP, conf.0)
-            .await
+        self.write_tracked(Register::INT_MAP, conf.0).await
     }
 
     /// Set interrupt pins polarity.
@@ -510,18 +803,7 @@ where
         &mut self,
         polarity: InterruptPolarity,
     ) -> Result<(), Error<PROTOCOL::ProtocolError>> {
-        let mut buf = self.protocol.read_register(Register::RANGE).await?;
-
-        match polarity {
-            InterruptPolarity::ActiveHigh => {
-                buf |= 0x40;
-            }
-            InterruptPolarity::ActiveLow => {
-                buf &= !0x40;
-            }
-        }
-
-        self.protocol.write_register(Register::RANGE, buf).await
+        self.apply(sans_io::interrupt_polarity_op(polarity)).await
     }
 
     /// Set I2C speed mode.
@@ -529,77 +811,381 @@ where
         &mut self,
         mode: I2cSpeedMode,
     ) -> Result<(), Error<PROTOCOL::ProtocolError>> {
-        let mut buf = self.protocol.read_register(Register::RANGE).await?;
+        self.apply(sans_io::i2c_speed_op(mode)).await
+    }
 
-        match mode {
-            I2cSpeedMode::HighSpeed => {
-                buf |= 0x80;
+    /// Set the synchronization mode the device will operate in. See options in [`ExternalSyncMode`].
+    pub async fn set_synchronization_mode(
+        &mut self,
+        mode: ExternalSyncMode,
+    ) -> Result<(), Error<PROTOCOL::ProtocolError>> {
+        self.write_tracked(Register::SYNC, mode as u8).await?;
+        self.external_sync.mode = mode;
+        Ok(())
+    }
+
+    /// Get the synchronization mode the device operates in, a reserved combination of the
+    /// SYNC register bits is reported as [`ExternalSyncError::ReservedMode`].
+    pub async fn get_synchronization_mode(
+        &mut self,
+    ) -> Result<ExternalSyncMode, Error<PROTOCOL::ProtocolError>> {
+        let value = self.protocol.read_register(Register::SYNC).await?;
+        ExternalSyncMode::try_from(value)
+            .map_err(|value| Error::InvalidExternalSync(ExternalSyncError::ReservedMode(value)))
+    }
+
+    /// Set the synchronization mode with the external clock frequency and the sync pulse rate,
+    /// after checking them against the current ODR with [`ExternalSync::validate`].
+    /// The driver uses them to compute the rates the device runs at.
+    pub async fn set_external_sync(
+        &mut self,
+        sync: ExternalSync,
+    ) -> Result<(), Error<PROTOCOL::ProtocolError>> {
+        let odr = self.read_reg::<Filter>().await?.odr().unwrap_or_default();
+        sync.validate(odr).map_err(Error::InvalidExternalSync)?;
+
+        self.write_tracked(Register::SYNC, sync.mode as u8).await?;
+        self.external_sync = sync;
+        Ok(())
+    }
+
+    /// Get the external synchronization configuration set with [`Self::set_external_sync`]
+    /// or [`Self::set_synchronization_mode`].
+    pub fn external_sync(&self) -> ExternalSync {
+        self.external_sync
+    }
+
+    /// Get the rate in Hertz at which the device outputs samples, taking the external clock
+    /// and synchronization into account, see [`ExternalSync::effective_odr`].
+    pub async fn get_effective_odr(&mut self) -> Result<f32, Error<PROTOCOL::ProtocolError>> {
+        let odr = self.read_reg::<Filter>().await?.odr().unwrap_or_default();
+        Ok(self.external_sync.effective_odr(odr))
+    }
+
+    /// Read the typed value of a register, see [`regs`].
+    pub async fn read_reg<T: RegisterValue>(
+        &mut self,
+    ) -> Result<T, Error<PROTOCOL::ProtocolError>> {
+        let mut buf = [0u8; 2];
+        let buf = &mut buf[..T::Bits::LEN];
+        self.protocol
+            .read_multiple_registers(T::REGISTER, buf)
+            .await?;
+        Ok(T::from_bits(T::Bits::from_bytes(buf)))
+    }
+
+    /// Write the typed value of a register, see [`regs`].
+    pub async fn write_reg<T: WritableRegister>(
+        &mut self,
+        value: T,
+    ) -> Result<(), Error<PROTOCOL::ProtocolError>> {
+        let mut buf = [0u8; 2];
+        let buf = &mut buf[..T::Bits::LEN];
+        value.to_bits().to_bytes(buf);
+        self.write_tracked_block(T::REGISTER, buf).await
+    }
+
+    /// Read a register, update its typed value with `f` and write it back.
+    /// The fields not set by `f`, reserved bits included, keep their value.
+    /// Returns the value written.
+    pub async fn modify_reg<T, F>(&mut self, f: F) -> Result<T, Error<PROTOCOL::ProtocolError>>
+    where
+        T: WritableRegister,
+        F: FnOnce(&mut T),
+    {
+        let mut value = self.read_reg::<T>().await?;
+        f(&mut value);
+        self.write_reg(value).await?;
+        Ok(value)
+    }
+
+    /// Write a register and record it in the [`ControlImage`].
+    async fn write_tracked(
+        &mut self,
+        register: Register,
+        value: u8,
+    ) -> Result<(), Error<PROTOCOL::ProtocolError>> {
+        self.protocol.write_register(register, value).await?;
+        self.written.record(register, &[value]);
+        Ok(())
+    }
+
+    /// Write registers from `start_register` and record them in the [`ControlImage`].
+    async fn write_tracked_block(
+        &mut self,
+        start_register: Register,
+        data: &[u8],
+    ) -> Result<(), Error<PROTOCOL::ProtocolError>> {
+        self.protocol.write_registers(start_register, data).await?;
+        self.written.record(start_register, data);
+        Ok(())
+    }
+
+    /// Write the [`ControlImage`] back to the device, in standby mode, then restore the mode.
+    async fn reapply_control_image(&mut self) -> Result<(), Error<PROTOCOL::ProtocolError>> {
+        let image = self.written;
+        let power_ctl = image.get(Register::POWER_CTL);
+
+        self.protocol
+            .write_register(
+                Register::POWER_CTL,
+                power_ctl.unwrap_or(0) | Mode::STANDBY_BIT,
+            )
+            .await?;
+        for (register, value) in image.iter() {
+            if register != Register::POWER_CTL {
+                self.protocol.write_register(register, value).await?;
             }
-            I2cSpeedMode::Fast => {
-                buf &= !0x80;
+        }
+        if let Some(power_ctl) = power_ctl {
+            self.protocol
+                .write_register(Register::POWER_CTL, power_ctl)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Execute a [`sans_io::SoftReset`] sequence.
+    async fn run_reset(
+        &mut self,
+        mut reset: sans_io::SoftReset,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), Error<PROTOCOL::ProtocolError>> {
+        loop {
+            match reset.action() {
+                ResetAction::WriteReset => {
+                    self.protocol
+                        .write_register(Register::RESET, sans_io::RESET_CODE)
+                        .await?;
+                    reset.reset_written();
+                }
+                ResetAction::Wait(us) => {
+                    delay.delay_us(us).await;
+                    reset.waited();
+                }
+                ResetAction::ReadStatus => {
+                    let status = self.get_status().await?;
+                    reset.status_read(status);
+                }
+                ResetAction::ReadShadowRegisters => {
+                    let shadow_values = self.get_shadow_values().await?;
+                    reset.shadow_registers_read(shadow_values);
+                }
+                ResetAction::Done => {
+                    self.written = ControlImage::after_reset();
+                    self.external_sync = ExternalSync::default();
+                    return Ok(());
+                }
+                ResetAction::Failed => return Err(Error::SoftResetFailed),
             }
         }
+    }
 
-        self.protocol.write_register(Register::RANGE, buf).await
+    /// Execute a [`RegisterOp`].
+    async fn apply(&mut self, op: RegisterOp) -> Result<(), Error<PROTOCOL::ProtocolError>> {
+        match op {
+            RegisterOp::Write(register, value) => self.write_tracked(register, value).await,
+            RegisterOp::Update {
+                register,
+                mask,
+                value,
+            } => {
+                let current = self.protocol.read_register(register).await?;
+                self.write_tracked(register, RegisterOp::updated_value(mask, value, current))
+                    .await
+            }
+        }
     }
 
-    /// Set the synchronization mode the device will operate in. See options in [`ExternalSyncMode`].
-    pub async fn set_synchronization_mode(
+    /// Set or clear the `mask` bits of the POWER_CTL register, keeping the other bits.
+    async fn update_power_ctl(
         &mut self,
-        mode: ExternalSyncMode,
+        mask: u8,
+        set: bool,
     ) -> Result<(), Error<PROTOCOL::ProtocolError>> {
-        self.protocol
-            .write_register(Register::SYNC, mode as u8)
-            .await
+        self.apply(sans_io::power_ctl_op(mask, set)).await
+    }
+
+    /// Read the FIFO with a [`sans_io::FifoParser`], handing each sample and its index to `store`.
+    async fn read_fifo(
+        &mut self,
+        capacity: usize,
+        store: impl FnMut(usize, I32x3),
+    ) -> Result<usize, Error<PROTOCOL::ProtocolError>> {
+        let fifo_entries = self.get_nb_samples_in_fifo().await?;
+        let mut parser = sans_io::FifoParser::new(fifo_entries, capacity)?;
+        self.run_fifo_parser(&mut parser, store).await?;
+
+        Ok(parser.retrieved())
+    }
+
+    /// Read the fifo like [`Self::read_fifo`], reporting the data lost in a [`FifoReadOutcome`].
+    async fn read_fifo_with_outcome(
+        &mut self,
+        capacity: usize,
+        store: impl FnMut(usize, I32x3),
+    ) -> Result<FifoReadOutcome, Error<PROTOCOL::ProtocolError>> {
+        let overrun = self.get_status().await?.is_fifo_overrun();
+        let fifo_entries = self.get_nb_samples_in_fifo().await?;
+        let mut outcome = FifoReadOutcome {
+            overrun,
+            ..Default::default()
+        };
+        if fifo_entries < 3 {
+            return Ok(outcome);
+        }
+
+        let mut parser = sans_io::FifoParser::new(fifo_entries, capacity)?;
+        match self.run_fifo_parser(&mut parser, store).await {
+            // No x-axis entry found, every entry read is reported as discarded.
+            Ok(()) | Err(Error::NotEnoughData) => {}
+            Err(e) => return Err(e),
+        }
+
+        outcome.samples = parser.retrieved();
+        outcome.discarded_entries = parser.discarded();
+        Ok(outcome)
+    }
+
+    /// Read the bytes designated by the [`sans_io::FifoParser`] until it is done.
+    async fn run_fifo_parser(
+        &mut self,
+        parser: &mut sans_io::FifoParser,
+        mut store: impl FnMut(usize, I32x3),
+    ) -> Result<(), Error<PROTOCOL::ProtocolError>> {
+        let mut buf = [0u8; 9];
+        while let Some(range) = parser.next_read() {
+            self.protocol
+                .read_multiple_registers(Register::FIFO_DATA, &mut buf[range])
+                .await?;
+            if let Some(sample) = parser.parse(&buf)? {
+                self.samples.update(sample);
+                store(parser.retrieved() - 1, sample);
+            }
+        }
+        Ok(())
+    }
+
+    /// Time for the filters to settle after entering measurement mode with the current filter
+    /// settings, see [`Config::settling_time_us`], on the clock the device runs on.
+    async fn settling_time_us(&mut self) -> Result<u64, Error<PROTOCOL::ProtocolError>> {
+        let filter = self.read_reg::<Filter>().await?;
+        let config = Config {
+            odr: filter.odr().unwrap_or_default(),
+            hpf: filter.hpf_corner().unwrap_or_default(),
+            ..Default::default()
+        };
+        Ok(self.external_sync.scale_time_us(config.settling_time_us()))
     }
 
-    /// Converts 9 bytes to raw acceleration sample.
-    fn acc_buf_to_raw_sample(buf: &[u8; 9], sample: &mut I32x3) {
-        sample.x =
-            (((buf[0] as i32) << 24) | ((buf[1] as i32) << 16) | ((buf[2] & 0xF0) as i32) << 8)
-                >> 12;
-        sample.y =
-            (((buf[3] as i32) << 24) | ((buf[4] as i32) << 16) | ((buf[5] & 0xF0) as i32) << 8)
-                >> 12;
-        sample.z =
-            (((buf[6] as i32) << 24) | ((buf[7] as i32) << 16) | ((buf[8] & 0xF0) as i32) << 8)
-                >> 12;
-    }
-
-    /// Converts 9 bytes to acceleration sample in g using the scale factor provided.
-    fn acc_buf_to_sample(buf: &[u8; 9], sample: &mut F32x3, scale_factor: f32) {
-        sample.x =
-            ((((buf[0] as i32) << 24) | ((buf[1] as i32) << 16) | ((buf[2] & 0xF0) as i32) << 8)
-                >> 12) as f32
-                * scale_factor;
-        sample.y =
-            ((((buf[3] as i32) << 24) | ((buf[4] as i32) << 16) | ((buf[5] & 0xF0) as i32) << 8)
-                >> 12) as f32
-                * scale_factor;
-        sample.z =
-            ((((buf[6] as i32) << 24) | ((buf[7] as i32) << 16) | ((buf[8] & 0xF0) as i32) << 8)
-                >> 12) as f32
-                * scale_factor;
+    /// Read and drop every entry in the fifo, returns the number of entries discarded.
+    async fn discard_fifo(&mut self) -> Result<u8, Error<PROTOCOL::ProtocolError>> {
+        let fifo_entries = self.get_nb_samples_in_fifo().await?;
+
+        let mut buf = [0u8; 9];
+        let mut left = usize::from(fifo_entries);
+        while left > 0 {
+            let entries = left.min(3);
+            self.protocol
+                .read_multiple_registers(Register::FIFO_DATA, &mut buf[..entries * 3])
+                .await?;
+            left -= entries;
+        }
+        Ok(fifo_entries)
+    }
+
+    /// Check the device identity and read the initial state of the device.
+    async fn init(&mut self) -> Result<(), Error<PROTOCOL::ProtocolError>> {
+        let buf = self.protocol.read_block(RegisterBlock::IDENTITY).await?;
+        self.info = DeviceInfo::from_registers(buf);
+        self.info.check::<VARIANT>().map_err(Error::BadIdentity)?;
+
+        self.init_shadow_values().await?;
+        self.range = self.get_range().await?;
+        Ok(())
     }
 
     /// Read the shadow registers initial values.
     async fn init_shadow_values(&mut self) -> Result<(), Error<PROTOCOL::ProtocolError>> {
-        self.protocol
-            .read_multiple_registers(Register::SHADOW_REG1, &mut self.shadow_values)
-            .await?;
+        self.shadow_values = self.protocol.read_block(RegisterBlock::SHADOW).await?;
         Ok(())
     }
 
     /// Get the shdow registers' values.
     async fn get_shadow_values(&mut self) -> Result<[u8; 5], Error<PROTOCOL::ProtocolError>> {
-        let mut shadow_values = [0u8; 5];
-        self.protocol
-            .read_multiple_registers(Register::SHADOW_REG1, &mut shadow_values)
-            .await?;
-        Ok(shadow_values)
+        self.protocol.read_block(RegisterBlock::SHADOW).await
     }
 }
 
+/// Operation run by [`Adxl355::with_standby`] while the device is in standby mode.
+///
+/// Async closures cannot borrow the driver they are given, so the operation is expressed
+/// as a type implementing this trait, e.g. a struct holding the new settings to apply.
+pub trait StandbyOp<PROTOCOL: Protocol, VARIANT: Variant = Adxl355Variant> {
+    type Output;
+
+    /// Run the operation on the device in standby mode.
+    async fn run(
+        self,
+        adxl355: &mut Adxl355<PROTOCOL, VARIANT>,
+    ) -> Result<Self::Output, Error<PROTOCOL::ProtocolError>>;
+}
+
+/// Run `future` until it completes, or until `timeout_us` elapsed on `delay` and return [`None`].
+async fn with_timeout<F: Future>(
+    future: F,
+    delay: &mut impl DelayNs,
+    timeout_us: u32,
+) -> Option<F::Output> {
+    let mut future = pin!(future);
+    let mut timeout = pin!(delay.delay_us(timeout_us));
+    poll_fn(|cx| {
+        if let Poll::Ready(output) = future.as_mut().poll(cx) {
+            return Poll::Ready(Some(output));
+        }
+        timeout.as_mut().poll(cx).map(|()| None)
+    })
+    .await
+}
+
+/// Wait until `pin` is high, or low when `active_high` is false.
+/// Returns [`Error::Timeout`] when it is not after `timeout_us`.
+async fn wait_pin<E: core::fmt::Debug>(
+    pin: &mut impl Wait,
+    active_high: bool,
+    delay: &mut impl DelayNs,
+    timeout_us: u32,
+) -> Result<(), Error<E>> {
+    let level = async {
+        if active_high {
+            pin.wait_for_high().await
+        } else {
+            pin.wait_for_low().await
+        }
+    };
+    match with_timeout(level, delay, timeout_us).await {
+        Some(result) => result.map_err(|_| Error::Pin),
+        None => Err(Error::Timeout),
+    }
+}
+
+/// Wait `time_us` with `delay`, in steps of up to [`u32::MAX`] microseconds.
+async fn delay_long_us(delay: &mut impl DelayNs, time_us: u64) {
+    let mut remaining_us = time_us;
+    while remaining_us > 0 {
+        let step = remaining_us.min(u32::MAX.into()) as u32;
+        delay.delay_us(step).await;
+        remaining_us -= u64::from(step);
+    }
+}
+
+/// [`DelayNs`] returning immediately, for the sequences run without delay.
+struct NoDelay;
+
+impl DelayNs for NoDelay {
+    async fn delay_ns(&mut self, _ns: u32) {}
+}
+
 pub trait Protocol {
     type ProtocolError: core::fmt::Debug;
 
@@ -621,17 +1207,45 @@ pub trait Protocol {
         buf: u8,
     ) -> Result<(), Error<Self::ProtocolError>>;
 
-    /// Write `buf.len() - 1` registers, the first byte must be the address of the start register.
-    async fn write_multiple_registers(
+    /// Write `data.len()` registers from the `start_register` address included.
+    async fn write_registers(
         &mut self,
-        buf: &mut [u8],
+        start_register: Register,
+        data: &[u8],
     ) -> Result<(), Error<Self::ProtocolError>>;
+
+    /// Read the registers of `block` in a single burst.
+    async fn read_block<const N: usize>(
+        &mut self,
+        block: RegisterBlock<N>,
+    ) -> Result<[u8; N], Error<Self::ProtocolError>> {
+        let mut buf = [0u8; N];
+        self.read_multiple_registers(block.start(), &mut buf)
+            .await?;
+        Ok(buf)
+    }
+
+    /// Write the registers of `block` in a single burst.
+    async fn write_block<const N: usize>(
+        &mut self,
+        block: RegisterBlock<N>,
+        data: &[u8; N],
+    ) -> Result<(), Error<Self::ProtocolError>> {
+        self.write_registers(block.start(), data).await
+    }
 }
 
 pub struct SpiProtocol<SPI> {
     spi: SPI,
 }
 
+impl<SPI> SpiProtocol<SPI> {
+    /// Create a SPI protocol from given SPI device.
+    pub fn new(spi: SPI) -> Self {
+        SpiProtocol { spi }
+    }
+}
+
 impl<SPI> Protocol for SpiProtocol<SPI>
 where
     SPI: SpiDevice,
@@ -683,13 +1297,19 @@ where
         Ok(())
     }
 
-    /// Write `buf.len() - 1` registers, the first byte must be the address of the register.
-    async fn write_multiple_registers(
+    /// Write `data.len()` registers from the `start_register` address included.
+    async fn write_registers(
         &mut self,
-        buf: &mut [u8],
+        start_register: Register,
+        data: &[u8],
     ) -> Result<(), Error<Self::ProtocolError>> {
-        buf[0] <<= 1;
-        self.spi.write(buf).await.map_err(Error::Protocol)?;
+        self.spi
+            .transaction(&mut [
+                spi::Operation::Write(&[start_register.addr() << 1]),
+                spi::Operation::Write(data),
+            ])
+            .await
+            .map_err(Error::Protocol)?;
         Ok(())
     }
 }
@@ -699,6 +1319,16 @@ pub struct I2cProtocol<I2C> {
     address: u8,
 }
 
+impl<I2C> I2cProtocol<I2C> {
+    /// Create an I2C protocol from given I2C peripheral and device address.
+    pub fn new(i2c: I2C, address: DeviceI2cAddress) -> Self {
+        I2cProtocol {
+            i2c,
+            address: address as u8,
+        }
+    }
+}
+
 impl<I2C> Protocol for I2cProtocol<I2C>
 where
     I2C: I2c,
@@ -744,38 +1374,22 @@ where
         Ok(())
     }
 
-    /// Write `buf.len() - 1` registers, the first byte must be the address of the register.
-    async fn write_multiple_registers(
+    /// Write `data.len()` registers from the `start_register` address included.
+    async fn write_registers(
         &mut self,
-        buf: &mut [u8],
+        start_register: Register,
+        data: &[u8],
     ) -> Result<(), Error<Self::ProtocolError>> {
         self.i2c
-            .write(self.address, buf)
+            .transaction(
+                self.address,
+                &mut [
+                    i2c::Operation::Write(&[start_register.addr()]),
+                    i2c::Operation::Write(data),
+                ],
+            )
             .await
             .map_err(Error::Protocol)?;
         Ok(())
     }
 }
-
-#[derive(Copy, Clone, Debug)]
-#[cfg_attr(feature = "defmt", derive(defmt::Format))]
-/// Driver errors
-pub enum Error<ProtocolError: core::fmt::Debug> {
-    /// Protocol error.
-    Protocol(ProtocolError),
-
-    /// Device ID is not 0xED.
-    BadDeviceId(u8),
-
-    /// Device is in measure mode and cannot be configured.
-    DeviceRunning,
-
-    /// Software reset failed to reset device properly.
-    SoftResetFailed,
-
-    /// The max number of samples to store in the fifo is invalid. Must range from 1 to 96.
-    InvalidMaxNbFifoSamples,
-
-    /// The fifo does not contain enough data to get one sample with the 3-axis values.
-    NotEnoughData,
-}
@@ -0,0 +1,209 @@
+// Copyright © 2023 Analog Devices, Inc. All Rights Reserved. This software is
+// proprietary to Analog Devices, Inc. and its licensors.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use adi_adxl355::retry::RetryAttempt;
+pub use adi_adxl355::retry::{
+    is_transient_i2c_error, is_transient_spi_error, BusStats, RetryPolicy,
+};
+use embedded_hal::{i2c, spi};
+use embedded_hal_async::delay::DelayNs;
+
+use crate::{Error, Protocol, Register};
+
+/// [`Protocol`] wrapper retrying the transactions that fail with a transient bus error.
+///
+/// A failure in the middle of a FIFO_DATA read may already have popped entries from the FIFO,
+/// the retried read then starts from a later entry. The FIFO readers check the x-axis marker
+/// of every sample, and drop the misaligned one before resynchronizing on the next x-axis entry.
+pub struct RetryingProtocol<P: Protocol, D> {
+    protocol: P,
+    delay: D,
+    policy: RetryPolicy,
+    is_retryable: fn(&P::ProtocolError) -> bool,
+    stats: BusStats,
+}
+
+impl<P, D> RetryingProtocol<P, D>
+where
+    P: Protocol,
+    D: DelayNs,
+{
+    /// Wrap `protocol`, retrying the errors for which `is_retryable` returns true.
+    pub fn new(
+        protocol: P,
+        delay: D,
+        policy: RetryPolicy,
+        is_retryable: fn(&P::ProtocolError) -> bool,
+    ) -> Self {
+        RetryingProtocol {
+            protocol,
+            delay,
+            policy,
+            is_retryable,
+            stats: BusStats::default(),
+        }
+    }
+
+    /// Get the bus error counters.
+    pub fn stats(&self) -> BusStats {
+        self.stats
+    }
+
+    /// Reset the bus error counters.
+    pub fn reset_stats(&mut self) {
+        self.stats = BusStats::default();
+    }
+
+    /// Get the retry policy.
+    pub fn policy(&self) -> RetryPolicy {
+        self.policy
+    }
+
+    /// Change the retry policy.
+    pub fn set_policy(&mut self, policy: RetryPolicy) {
+        self.policy = policy;
+    }
+
+    /// Release the wrapped protocol and delay.
+    pub fn into_inner(self) -> (P, D) {
+        (self.protocol, self.delay)
+    }
+
+    /// Record a failed attempt and wait before the retry, or give back the error.
+    async fn retry_after(
+        &mut self,
+        attempt: &mut RetryAttempt,
+        error: Error<P::ProtocolError>,
+    ) -> Result<(), Error<P::ProtocolError>> {
+        let retryable = matches!(&error, Error::Protocol(e) if (self.is_retryable)(e));
+        match attempt.failed(retryable, &mut self.stats) {
+            Some(backoff_us) => {
+                self.delay.delay_us(backoff_us).await;
+                Ok(())
+            }
+            None => Err(error),
+        }
+    }
+}
+
+impl<P, D> RetryingProtocol<P, D>
+where
+    P: Protocol,
+    P::ProtocolError: i2c::Error,
+    D: DelayNs,
+{
+    /// Wrap an I2C `protocol`, retrying the errors accepted by [`is_transient_i2c_error`].
+    pub fn for_i2c(protocol: P, delay: D, policy: RetryPolicy) -> Self {
+        Self::new(protocol, delay, policy, |error| {
+            is_transient_i2c_error(i2c::Error::kind(error))
+        })
+    }
+}
+
+impl<P, D> RetryingProtocol<P, D>
+where
+    P: Protocol,
+    P::ProtocolError: spi::Error,
+    D: DelayNs,
+{
+    /// Wrap a SPI `protocol`, retrying the errors accepted by [`is_transient_spi_error`].
+    pub fn for_spi(protocol: P, delay: D, policy: RetryPolicy) -> Self {
+        Self::new(protocol, delay, policy, |error| {
+            is_transient_spi_error(spi::Error::kind(error))
+        })
+    }
+}
+
+impl<P, D> Protocol for RetryingProtocol<P, D>
+where
+    P: Protocol,
+    D: DelayNs,
+{
+    type ProtocolError = P::ProtocolError;
+
+    /// Read a single register.
+    async fn read_register(
+        &mut self,
+        register: Register,
+    ) -> Result<u8, Error<Self::ProtocolError>> {
+        let mut attempt = RetryAttempt::new(self.policy);
+        loop {
+            match self.protocol.read_register(register).await {
+                Ok(value) => {
+                    attempt.succeeded(&mut self.stats);
+                    return Ok(value);
+                }
+                Err(error) => self.retry_after(&mut attempt, error).await?,
+            }
+        }
+    }
+
+    /// Read `buf.len()` registers from the `start_register` address included.
+    async fn read_multiple_registers(
+        &mut self,
+        start_register: Register,
+        buf: &mut [u8],
+    ) -> Result<(), Error<Self::ProtocolError>> {
+        let mut attempt = RetryAttempt::new(self.policy);
+        loop {
+            match self
+                .protocol
+                .read_multiple_registers(start_register, buf)
+                .await
+            {
+                Ok(()) => {
+                    attempt.succeeded(&mut self.stats);
+                    return Ok(());
+                }
+                Err(error) => self.retry_after(&mut attempt, error).await?,
+            }
+        }
+    }
+
+    /// Write a single register.
+    async fn write_register(
+        &mut self,
+        register: Register,
+        buf: u8,
+    ) -> Result<(), Error<Self::ProtocolError>> {
+        let mut attempt = RetryAttempt::new(self.policy);
+        loop {
+            match self.protocol.write_register(register, buf).await {
+                Ok(()) => {
+                    attempt.succeeded(&mut self.stats);
+                    return Ok(());
+                }
+                Err(error) => self.retry_after(&mut attempt, error).await?,
+            }
+        }
+    }
+
+    /// Write `data.len()` registers from the `start_register` address included.
+    async fn write_registers(
+        &mut self,
+        start_register: Register,
+        data: &[u8],
+    ) -> Result<(), Error<Self::ProtocolError>> {
+        let mut attempt = RetryAttempt::new(self.policy);
+        loop {
+            match self.protocol.write_registers(start_register, data).await {
+                Ok(()) => {
+                    attempt.succeeded(&mut self.stats);
+                    return Ok(());
+                }
+                Err(error) => self.retry_after(&mut attempt, error).await?,
+            }
+        }
+    }
+}
@@ -0,0 +1,113 @@
+// Copyright © 2023 Analog Devices, Inc. All Rights Reserved. This software is
+// proprietary to Analog Devices, Inc. and its licensors.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub use adi_adxl355::trace::{Direction, TraceBuffer, TraceEntry, TRACE_ENTRY_BYTES};
+
+use crate::{Error, Protocol, Register};
+
+/// [`Protocol`] wrapper recording every transaction in a [`TraceBuffer`] of the last `N`
+/// entries, and to `defmt` or `log` when these features are enabled.
+pub struct TracingProtocol<P, const N: usize> {
+    protocol: P,
+    buffer: TraceBuffer<N>,
+}
+
+impl<P, const N: usize> TracingProtocol<P, N>
+where
+    P: Protocol,
+{
+    /// Wrap `protocol` with an empty trace buffer.
+    pub fn new(protocol: P) -> Self {
+        TracingProtocol {
+            protocol,
+            buffer: TraceBuffer::new(),
+        }
+    }
+
+    /// Get the recorded transactions.
+    pub fn trace(&self) -> &TraceBuffer<N> {
+        &self.buffer
+    }
+
+    /// Remove the recorded transactions.
+    pub fn clear_trace(&mut self) {
+        self.buffer.clear();
+    }
+
+    /// Release the wrapped protocol.
+    pub fn into_inner(self) -> P {
+        self.protocol
+    }
+
+    /// Record a transaction.
+    fn record(&mut self, register: Register, direction: Direction, data: &[u8], succeeded: bool) {
+        let entry = TraceEntry::new(register, direction, data, succeeded);
+        entry.log();
+        self.buffer.push(entry);
+    }
+}
+
+impl<P, const N: usize> Protocol for TracingProtocol<P, N>
+where
+    P: Protocol,
+{
+    type ProtocolError = P::ProtocolError;
+
+    /// Read a single register.
+    async fn read_register(
+        &mut self,
+        register: Register,
+    ) -> Result<u8, Error<Self::ProtocolError>> {
+        let result = self.protocol.read_register(register).await;
+        let value = *result.as_ref().unwrap_or(&0);
+        self.record(register, Direction::Read, &[value], result.is_ok());
+        result
+    }
+
+    /// Read `buf.len()` registers from the `start_register` address included.
+    async fn read_multiple_registers(
+        &mut self,
+        start_register: Register,
+        buf: &mut [u8],
+    ) -> Result<(), Error<Self::ProtocolError>> {
+        let result = self
+            .protocol
+            .read_multiple_registers(start_register, buf)
+            .await;
+        self.record(start_register, Direction::Read, buf, result.is_ok());
+        result
+    }
+
+    /// Write a single register.
+    async fn write_register(
+        &mut self,
+        register: Register,
+        buf: u8,
+    ) -> Result<(), Error<Self::ProtocolError>> {
+        let result = self.protocol.write_register(register, buf).await;
+        self.record(register, Direction::Write, &[buf], result.is_ok());
+        result
+    }
+
+    /// Write `data.len()` registers from the `start_register` address included.
+    async fn write_registers(
+        &mut self,
+        start_register: Register,
+        data: &[u8],
+    ) -> Result<(), Error<Self::ProtocolError>> {
+        let result = self.protocol.write_registers(start_register, data).await;
+        self.record(start_register, Direction::Write, data, result.is_ok());
+        result
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright © 2023 Analog Devices, Inc. All Rights Reserved. This software is
+// proprietary to Analog Devices, Inc. and its licensors.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of the [`accelerometer`] crate traits, enabled with the `accelerometer`
+//! feature.
+//!
+//! The traits take the vectors of the micromath version the [`accelerometer`] crate depends on,
+//! [`accelerometer::vector`], which differ from the micromath vectors returned by the driver.
+//! The samples are converted field by field.
+
+use accelerometer::vector::{F32x3, I32x3};
+use accelerometer::{Accelerometer, ErrorKind, RawAccelerometer};
+
+use crate::variant::Variant;
+use crate::{Adxl355, Error, Protocol};
+
+impl<PROTOCOL, VARIANT> Accelerometer for Adxl355<PROTOCOL, VARIANT>
+where
+    PROTOCOL: Protocol,
+    VARIANT: Variant,
+{
+    type Error = Error<PROTOCOL::ProtocolError>;
+
+    /// Acceleration in g, see [`Adxl355::get_accel_sample`].
+    fn accel_norm(&mut self) -> Result<F32x3, accelerometer::Error<Self::Error>> {
+        self.get_accel_sample()
+            .map(|sample| F32x3 {
+                x: sample.x,
+                y: sample.y,
+                z: sample.z,
+            })
+            .map_err(accelerometer_error)
+    }
+
+    /// Output data rate in Hz, see [`Adxl355::get_effective_odr`].
+    fn sample_rate(&mut self) -> Result<f32, accelerometer::Error<Self::Error>> {
+        self.get_effective_odr().map_err(accelerometer_error)
+    }
+}
+
+impl<PROTOCOL, VARIANT> RawAccelerometer<I32x3> for Adxl355<PROTOCOL, VARIANT>
+where
+    PROTOCOL: Protocol,
+    VARIANT: Variant,
+{
+    type Error = Error<PROTOCOL::ProtocolError>;
+
+    /// Raw acceleration, see [`Adxl355::get_raw_accel_sample`].
+    fn accel_raw(&mut self) -> Result<I32x3, accelerometer::Error<Self::Error>> {
+        self.get_raw_accel_sample()
+            .map(|sample| I32x3 {
+                x: sample.x,
+                y: sample.y,
+                z: sample.z,
+            })
+            .map_err(accelerometer_error)
+    }
+}
+
+/// Wrap a driver error in an [`accelerometer::Error`] of the matching [`ErrorKind`].
+fn accelerometer_error<E: core::fmt::Debug>(error: Error<E>) -> accelerometer::Error<Error<E>> {
+    let kind = match error {
+        Error::Protocol(_) => ErrorKind::Bus,
+        Error::BadDeviceId(_)
+        | Error::BadIdentity(_)
+        | Error::SoftResetFailed
+        | Error::NotEnoughData
+        | Error::Timeout
+        | Error::Pin => ErrorKind::Device,
+        Error::DeviceRunning | Error::TemperatureDisabled => ErrorKind::Mode,
+        Error::InvalidMaxNbFifoSamples
+        | Error::UnsupportedRange
+        | Error::InvalidExternalSync(_) => ErrorKind::Param,
+    };
+    accelerometer::Error::new_with_cause(kind, error)
+}
@@ -0,0 +1,227 @@
+// Copyright © 2023 Analog Devices, Inc. All Rights Reserved. This software is
+// proprietary to Analog Devices, Inc. and its licensors.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Continuous acquisition through the FIFO watermark interrupt.
+//!
+//! The drivers' `start_acquisition` sets the FIFO watermark from an [`AcquisitionConfig`] and
+//! maps the FIFO_FULL interrupt on INT1, then `fill_buffer` fills a buffer from the FIFO each
+//! time INT1 signals the watermark, counting the samples and the data lost in the
+//! [`AcquisitionStats`] of the [`Acquisition`]. A [`DoubleBuffer`] lets the application process
+//! a full buffer while the other one is filled.
+
+use crate::config::Odr;
+use crate::fifo::FifoReadOutcome;
+use crate::interrupt::InterruptPolarity;
+use crate::sans_io::FIFO_MAX_ENTRIES;
+use crate::sync::ExternalSync;
+
+/// Number of samples the FIFO can hold.
+pub const FIFO_CAPACITY_SAMPLES: u8 = FIFO_MAX_ENTRIES / 3;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Watermark, timeout and settling wait of a continuous acquisition.
+pub struct AcquisitionConfig {
+    /// Number of samples in the FIFO that trigger the FIFO_FULL interrupt, from 1 to 32.
+    pub watermark_samples: u8,
+    /// Longest wait for the FIFO_FULL interrupt before returning [`crate::Error::Timeout`].
+    pub timeout_us: u32,
+    /// Longest wait for the filters to settle when the acquisition starts, 0 to start without
+    /// waiting, see [`crate::Config::settling_time_us`].
+    pub max_settling_us: u32,
+}
+
+impl AcquisitionConfig {
+    /// Highest watermark chosen by [`Self::for_latency`], leaving 8 samples of headroom to read
+    /// the FIFO before it overruns.
+    pub const MAX_WATERMARK_SAMPLES: u8 = 24;
+
+    /// Creates an acquisition configuration with the provided values, starting without waiting
+    /// for the filters to settle.
+    pub fn new(watermark_samples: u8, timeout_us: u32) -> Self {
+        AcquisitionConfig {
+            watermark_samples,
+            timeout_us,
+            max_settling_us: 0,
+        }
+    }
+
+    /// Watermark delivering the samples at most `latency_us` after they are measured at the
+    /// rate the device outputs them with the given ODR setting and `sync`, up to
+    /// [`Self::MAX_WATERMARK_SAMPLES`]. The timeout is twice the time to reach the watermark and
+    /// the acquisition starts without waiting for the filters to settle.
+    pub fn for_latency(odr: Odr, sync: &ExternalSync, latency_us: u32) -> Self {
+        let period_us = sync.period_us(odr);
+        let samples =
+            ((latency_us as f32 / period_us) as u32).clamp(1, Self::MAX_WATERMARK_SAMPLES.into());
+        AcquisitionConfig {
+            watermark_samples: samples as u8,
+            // Saturates on overflow.
+            timeout_us: ((2 * samples) as f32 * period_us) as u32,
+            max_settling_us: 0,
+        }
+    }
+
+    /// Watermark as the number of FIFO entries expected by the FIFO_SAMPLES register.
+    pub fn fifo_samples(&self) -> u8 {
+        self.watermark_samples.saturating_mul(3)
+    }
+
+    /// Time to reach the watermark with the given ODR setting and `sync`.
+    pub fn latency_us(&self, odr: Odr, sync: &ExternalSync) -> u32 {
+        (f32::from(self.watermark_samples) * sync.period_us(odr)) as u32
+    }
+
+    /// Time left to read the FIFO after the interrupt before it overruns with the given ODR
+    /// setting and `sync`.
+    pub fn headroom_us(&self, odr: Odr, sync: &ExternalSync) -> u32 {
+        let samples = FIFO_CAPACITY_SAMPLES.saturating_sub(self.watermark_samples);
+        (f32::from(samples) * sync.period_us(odr)) as u32
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Counters of a continuous acquisition.
+pub struct AcquisitionStats {
+    /// Number of FIFO reads.
+    pub batches: u32,
+    /// Number of samples retrieved.
+    pub samples: u64,
+    /// Number of buffers filled.
+    pub buffers: u32,
+    /// Number of FIFO reads that found the FIFO overrun, each losing samples.
+    pub overruns: u32,
+    /// Number of FIFO entries dropped to resynchronize on complete samples.
+    pub discarded_entries: u32,
+}
+
+impl AcquisitionStats {
+    /// No data was lost since the acquisition started.
+    pub fn is_gap_free(&self) -> bool {
+        self.overruns == 0 && self.discarded_entries == 0
+    }
+
+    /// Average number of samples retrieved per second over `elapsed_us`, measured by the
+    /// application since the acquisition started.
+    pub fn samples_per_second(&self, elapsed_us: u64) -> f32 {
+        if elapsed_us == 0 {
+            return 0.0;
+        }
+        self.samples as f32 * 1_000_000.0 / elapsed_us as f32
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// State of a continuous acquisition, passed to the drivers' `start_acquisition` and
+/// `fill_buffer`.
+pub struct Acquisition {
+    config: AcquisitionConfig,
+    polarity: InterruptPolarity,
+    stats: AcquisitionStats,
+}
+
+impl Acquisition {
+    /// Creates an acquisition with the provided configuration.
+    pub fn new(config: AcquisitionConfig) -> Self {
+        Acquisition {
+            config,
+            polarity: InterruptPolarity::ActiveLow,
+            stats: AcquisitionStats::default(),
+        }
+    }
+
+    /// Get the acquisition configuration.
+    pub fn config(&self) -> AcquisitionConfig {
+        self.config
+    }
+
+    /// Get the polarity of the INT1 pin, read when the acquisition started.
+    pub fn interrupt_polarity(&self) -> InterruptPolarity {
+        self.polarity
+    }
+
+    /// Get the counters of the acquisition.
+    pub fn stats(&self) -> AcquisitionStats {
+        self.stats
+    }
+
+    /// The acquisition started with the given INT1 polarity, the counters are reset.
+    pub fn start(&mut self, polarity: InterruptPolarity) {
+        self.polarity = polarity;
+        self.stats = AcquisitionStats::default();
+    }
+
+    /// The FIFO was read. The counters saturate instead of wrapping on long-running captures.
+    pub fn record(&mut self, outcome: &FifoReadOutcome) {
+        let stats = &mut self.stats;
+        stats.batches = stats.batches.saturating_add(1);
+        stats.samples = stats.samples.saturating_add(outcome.samples as u64);
+        stats.overruns = stats.overruns.saturating_add(outcome.overrun.into());
+        stats.discarded_entries = stats
+            .discarded_entries
+            .saturating_add(outcome.discarded_entries.into());
+    }
+
+    /// A buffer was filled.
+    pub fn buffer_filled(&mut self) {
+        self.stats.buffers = self.stats.buffers.saturating_add(1);
+    }
+}
+
+/// Two caller-provided buffers, one being filled while the other one, full, is processed.
+///
+/// [`DoubleBuffer::split`] borrows both at once, so that an async application can run the
+/// driver's `fill_buffer` on one and its processing on the other concurrently, then
+/// [`DoubleBuffer::swap`] them.
+pub struct DoubleBuffer<'a, T> {
+    buffers: [&'a mut [T]; 2],
+    filling: usize,
+}
+
+impl<'a, T> DoubleBuffer<'a, T> {
+    /// Creates a double buffer, `first` is filled first.
+    pub fn new(first: &'a mut [T], second: &'a mut [T]) -> Self {
+        DoubleBuffer {
+            buffers: [first, second],
+            filling: 0,
+        }
+    }
+
+    /// Get the buffer to fill.
+    pub fn filling(&mut self) -> &mut [T] {
+        self.buffers[self.filling]
+    }
+
+    /// Get the buffer filled before the last [`Self::swap`].
+    pub fn ready(&self) -> &[T] {
+        self.buffers[1 - self.filling]
+    }
+
+    /// Get the buffer to fill and the ready buffer.
+    pub fn split(&mut self) -> (&mut [T], &[T]) {
+        let [first, second] = &mut self.buffers;
+        if self.filling == 0 {
+            (first, second)
+        } else {
+            (second, first)
+        }
+    }
+
+    /// The buffer being filled is full, it becomes the ready buffer.
+    pub fn swap(&mut self) {
+        self.filling = 1 - self.filling;
+    }
+}
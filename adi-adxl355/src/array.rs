@@ -0,0 +1,334 @@
+// Copyright © 2023 Analog Devices, Inc. All Rights Reserved. This software is
+// proprietary to Analog Devices, Inc. and its licensors.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Array of sensors read as aligned multi-channel frames.
+
+use micromath::vector::I32x3;
+
+use crate::config::Config;
+use crate::fifo::{FifoReadOutcome, FlushMethod};
+use crate::health::{HealthPolicy, HealthReport};
+use crate::sync::ExternalSync;
+use crate::variant::Variant;
+use crate::{delay_long_us, Adxl355, Error, Protocol};
+use embedded_hal::delay::DelayNs;
+
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
+#[cfg_attr(feature = "std", error("sensor {sensor}: {error}"))]
+/// Error of one sensor of an array.
+pub struct ArrayError<ProtocolError: core::fmt::Debug> {
+    /// Index of the sensor in the array.
+    pub sensor: usize,
+    /// Error of the sensor.
+    pub error: Error<ProtocolError>,
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// State of one sensor after an array read.
+pub struct SensorFlags {
+    /// The sensor could not be read, its channel holds zeros and its error is in
+    /// [`ArrayReadOutcome::errors`].
+    pub failed: bool,
+    /// The fifo of the sensor overran before the read.
+    pub overrun: bool,
+    /// Number of fifo entries dropped to resynchronize on complete samples.
+    pub discarded_entries: u8,
+    /// Number of samples read beyond the aligned frames and dropped, the sensor is ahead of
+    /// the others by as many samples.
+    pub dropped_samples: usize,
+}
+
+impl SensorFlags {
+    /// No data was lost and the sensor stays aligned with the others.
+    pub fn is_lossless(&self) -> bool {
+        !self.failed && !self.overrun && self.discarded_entries == 0 && self.dropped_samples == 0
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Outcome of an array read.
+pub struct ArrayReadOutcome<ProtocolError: core::fmt::Debug, const N: usize> {
+    /// Number of frames retrieved, each holding one sample per sensor.
+    pub frames: usize,
+    /// State of each sensor.
+    pub sensors: [SensorFlags; N],
+    /// Error of each sensor which failed, `None` for the others.
+    pub errors: [Option<Error<ProtocolError>>; N],
+}
+
+impl<ProtocolError: core::fmt::Debug, const N: usize> ArrayReadOutcome<ProtocolError, N> {
+    /// No data was lost and the sensors stay aligned.
+    pub fn is_lossless(&self) -> bool {
+        self.sensors.iter().all(SensorFlags::is_lossless)
+    }
+
+    /// Number of complete samples to read from each sensor for the frames to stay aligned:
+    /// the smallest number of complete samples in the fifos of the sensors not failed,
+    /// within `capacity` frames.
+    pub fn aligned_samples(
+        fifo_entries: &[Result<u8, Error<ProtocolError>>; N],
+        capacity: usize,
+    ) -> usize {
+        fifo_entries
+            .iter()
+            .flatten()
+            .map(|&entries| usize::from(entries / 3))
+            .min()
+            .unwrap_or(0)
+            .min(capacity)
+    }
+
+    /// Build the outcome from the fifo read of each sensor, or the error of a sensor which
+    /// failed. The frames are limited by the sensor which retrieved the fewest samples.
+    pub fn from_fifo_reads(reads: [Result<FifoReadOutcome, Error<ProtocolError>>; N]) -> Self {
+        let frames = reads
+            .iter()
+            .flatten()
+            .map(|read| read.samples)
+            .min()
+            .unwrap_or(0);
+        let mut sensors = [SensorFlags::default(); N];
+        let mut errors = core::array::from_fn(|_| None);
+        for (sensor, read) in reads.into_iter().enumerate() {
+            match read {
+                Ok(read) => {
+                    sensors[sensor] = SensorFlags {
+                        failed: false,
+                        overrun: read.overrun,
+                        discarded_entries: read.discarded_entries,
+                        dropped_samples: read.samples - frames,
+                    }
+                }
+                Err(error) => {
+                    sensors[sensor].failed = true;
+                    errors[sensor] = Some(error);
+                }
+            }
+        }
+        ArrayReadOutcome {
+            frames,
+            sensors,
+            errors,
+        }
+    }
+}
+
+/// Array of `N` ADXL355 sensors, typically on separate chip selects, configured and read
+/// together. The fifos are read into frames holding one sample per sensor.
+///
+/// The sensors are started one after the other as fast as the bus allows, they sample at the
+/// same instants when they share an external clock and sync, see [`Self::set_external_sync`].
+pub struct Adxl355Array<PROTOCOL: Protocol, VARIANT: Variant, const N: usize> {
+    sensors: [Adxl355<PROTOCOL, VARIANT>; N],
+}
+
+impl<PROTOCOL, VARIANT, const N: usize> Adxl355Array<PROTOCOL, VARIANT, N>
+where
+    PROTOCOL: Protocol,
+    VARIANT: Variant,
+{
+    /// Create an array from the drivers of its sensors.
+    pub fn new(sensors: [Adxl355<PROTOCOL, VARIANT>; N]) -> Self {
+        Adxl355Array { sensors }
+    }
+
+    /// Get the drivers of the sensors.
+    pub fn sensors(&mut self) -> &mut [Adxl355<PROTOCOL, VARIANT>; N] {
+        &mut self.sensors
+    }
+
+    /// Release the drivers of the sensors.
+    pub fn into_inner(self) -> [Adxl355<PROTOCOL, VARIANT>; N] {
+        self.sensors
+    }
+
+    /// Apply the same configuration to every sensor.
+    pub fn configure(&mut self, config: Config) -> Result<(), ArrayError<PROTOCOL::ProtocolError>> {
+        for (sensor, acc) in self.sensors.iter_mut().enumerate() {
+            acc.configure(config)
+                .map_err(|error| ArrayError { sensor, error })?;
+        }
+        Ok(())
+    }
+
+    /// Apply the same external synchronization to every sensor, to have them sample at the
+    /// same instants.
+    pub fn set_external_sync(
+        &mut self,
+        sync: ExternalSync,
+    ) -> Result<(), ArrayError<PROTOCOL::ProtocolError>> {
+        for (sensor, acc) in self.sensors.iter_mut().enumerate() {
+            acc.set_external_sync(sync)
+                .map_err(|error| ArrayError { sensor, error })?;
+        }
+        Ok(())
+    }
+
+    /// Set every sensor in measurement mode back to back, wait for the filters of all of them
+    /// to settle for at most `max_settling_us`, then empty their fifos back to back, so that
+    /// they hold aligned samples. Returns the settling time left in microseconds, see
+    /// [`Adxl355::start_measurement_and_settle`].
+    pub fn start(
+        &mut self,
+        delay: &mut impl DelayNs,
+        max_settling_us: u64,
+    ) -> Result<u64, ArrayError<PROTOCOL::ProtocolError>> {
+        let mut settling_time_us = 0;
+        for (sensor, acc) in self.sensors.iter_mut().enumerate() {
+            let sensor_settling_us = acc
+                .settling_time_us()
+                .map_err(|error| ArrayError { sensor, error })?;
+            settling_time_us = settling_time_us.max(sensor_settling_us);
+        }
+
+        for (sensor, acc) in self.sensors.iter_mut().enumerate() {
+            acc.set_measurement_mode()
+                .map_err(|error| ArrayError { sensor, error })?;
+        }
+        let wait_us = settling_time_us.min(max_settling_us);
+        delay_long_us(delay, wait_us);
+        for (sensor, acc) in self.sensors.iter_mut().enumerate() {
+            acc.flush_fifo(FlushMethod::Drain)
+                .map_err(|error| ArrayError { sensor, error })?;
+        }
+        Ok(settling_time_us - wait_us)
+    }
+
+    /// Set every sensor in standby mode.
+    pub fn stop(&mut self) -> Result<(), ArrayError<PROTOCOL::ProtocolError>> {
+        for (sensor, acc) in self.sensors.iter_mut().enumerate() {
+            acc.set_standby_mode()
+                .map_err(|error| ArrayError { sensor, error })?;
+        }
+        Ok(())
+    }
+
+    /// Read the fifos into `frames`, the same number of samples from each sensor.
+    /// A sensor failing is flagged in the returned [`ArrayReadOutcome`] along with its error,
+    /// and the others are still read.
+    pub fn read_frames(
+        &mut self,
+        frames: &mut [[I32x3; N]],
+    ) -> ArrayReadOutcome<PROTOCOL::ProtocolError, N> {
+        let mut fifo_entries: [_; N] =
+            core::array::from_fn(|sensor| self.sensors[sensor].get_nb_samples_in_fifo());
+        let samples = ArrayReadOutcome::aligned_samples(&fifo_entries, frames.len());
+
+        let reads = core::array::from_fn(|sensor| {
+            core::mem::replace(&mut fifo_entries[sensor], Ok(0)).and_then(|_| {
+                self.sensors[sensor]
+                    .read_fifo_with_outcome(samples, |index, sample| frames[index][sensor] = sample)
+            })
+        });
+
+        let outcome = ArrayReadOutcome::from_fifo_reads(reads);
+        for (sensor, flags) in outcome.sensors.iter().enumerate() {
+            if flags.failed {
+                for frame in frames[..outcome.frames].iter_mut() {
+                    frame[sensor] = I32x3::default();
+                }
+            }
+        }
+        outcome
+    }
+
+    /// Check the health of every sensor, see [`Adxl355::health_check`].
+    pub fn health_check(
+        &mut self,
+        policy: HealthPolicy,
+    ) -> [Result<HealthReport, Error<PROTOCOL::ProtocolError>>; N] {
+        core::array::from_fn(|sensor| self.sensors[sensor].health_check(policy))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Outcome<const N: usize> = ArrayReadOutcome<(), N>;
+
+    fn read(samples: usize) -> Result<FifoReadOutcome, Error<()>> {
+        Ok(FifoReadOutcome {
+            samples,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn aligned_samples_follow_the_emptiest_fifo() {
+        assert_eq!(Outcome::aligned_samples(&[Ok(30), Ok(12), Ok(96)], 32), 4);
+        // Incomplete samples are left in the fifo.
+        assert_eq!(Outcome::aligned_samples(&[Ok(14), Ok(17)], 32), 4);
+        assert_eq!(Outcome::aligned_samples(&[Ok(96), Ok(90)], 8), 8);
+        // The failed sensors are skipped.
+        assert_eq!(
+            Outcome::aligned_samples(&[Err(Error::Timeout), Ok(9)], 32),
+            3
+        );
+        assert_eq!(
+            Outcome::aligned_samples(&[Err(Error::Timeout), Err(Error::Timeout)], 32),
+            0
+        );
+        assert_eq!(Outcome::aligned_samples(&[], 32), 0);
+    }
+
+    #[test]
+    fn frames_follow_the_sensor_with_the_fewest_samples() {
+        let outcome = Outcome::from_fifo_reads([
+            read(10),
+            Ok(FifoReadOutcome {
+                samples: 8,
+                overrun: true,
+                discarded_entries: 2,
+            }),
+            read(9),
+        ]);
+        assert_eq!(outcome.frames, 8);
+        assert_eq!(outcome.sensors[0].dropped_samples, 2);
+        assert_eq!(
+            outcome.sensors[1],
+            SensorFlags {
+                failed: false,
+                overrun: true,
+                discarded_entries: 2,
+                dropped_samples: 0,
+            }
+        );
+        assert_eq!(outcome.sensors[2].dropped_samples, 1);
+        assert!(outcome.errors.iter().all(Option::is_none));
+        assert!(!outcome.is_lossless());
+    }
+
+    #[test]
+    fn failed_sensors_do_not_limit_the_frames() {
+        let outcome = Outcome::from_fifo_reads([read(6), Err(Error::Timeout), read(6)]);
+        assert_eq!(outcome.frames, 6);
+        assert!(!outcome.sensors[0].failed);
+        assert!(outcome.sensors[1].failed);
+        assert!(matches!(outcome.errors[1], Some(Error::Timeout)));
+        assert!(outcome.errors[0].is_none());
+        assert!(!outcome.is_lossless());
+
+        let outcome = Outcome::from_fifo_reads([read(6), read(6)]);
+        assert_eq!(outcome.frames, 6);
+        assert!(outcome.is_lossless());
+
+        let outcome = Outcome::<1>::from_fifo_reads([Err(Error::Timeout)]);
+        assert_eq!(outcome.frames, 0);
+    }
+}
@@ -0,0 +1,287 @@
+// Copyright © 2023 Analog Devices, Inc. All Rights Reserved. This software is
+// proprietary to Analog Devices, Inc. and its licensors.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bus throughput budget of a configuration.
+//!
+//! [`BusBudget::plan`] computes the bus time and bytes the drivers need to keep up with the rate
+//! a [`Config`] outputs samples at with an [`ExternalSync`] over a [`Transport`], counting the
+//! transactions and their framing as issued by [`crate::SpiProtocol`] and
+//! [`crate::I2cProtocol`], and recommends a FIFO watermark.
+
+use crate::acquisition::{AcquisitionConfig, FIFO_CAPACITY_SAMPLES};
+use crate::config::Config;
+use crate::sync::ExternalSync;
+
+/// Registers read per sample, the 3 axes of 3 bytes.
+const SAMPLE_BYTES: u32 = 9;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Bus connecting the device.
+pub enum TransportKind {
+    /// SPI, one command byte addressing the registers of each transaction.
+    Spi,
+    /// I2C, device address and register address ahead of a repeated start for each read.
+    I2c,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Bus type and speed used to plan a [`BusBudget`].
+pub struct Transport {
+    /// Bus type, which sets the framing of the transactions.
+    pub kind: TransportKind,
+    /// Bus clock frequency in Hertz.
+    pub clock_hz: u32,
+    /// Time spent per transaction besides the bus transfer, such as chip select setup or
+    /// driver latency, 0 by default.
+    pub transaction_overhead_us: u32,
+}
+
+impl Transport {
+    /// SPI bus at the given clock.
+    pub fn spi(clock_hz: u32) -> Self {
+        Transport {
+            kind: TransportKind::Spi,
+            clock_hz,
+            transaction_overhead_us: 0,
+        }
+    }
+
+    /// I2C bus at the given clock.
+    pub fn i2c(clock_hz: u32) -> Self {
+        Transport {
+            kind: TransportKind::I2c,
+            clock_hz,
+            transaction_overhead_us: 0,
+        }
+    }
+
+    /// Sets the time spent per transaction besides the bus transfer.
+    pub fn transaction_overhead_us(&mut self, overhead_us: u32) -> &mut Self {
+        self.transaction_overhead_us = overhead_us;
+        self
+    }
+
+    /// Bytes on the bus to read `len` registers, framing included: the address byte for SPI,
+    /// the device address twice and the register address for I2C.
+    pub fn read_bytes(&self, len: u32) -> u32 {
+        match self.kind {
+            TransportKind::Spi => 1 + len,
+            TransportKind::I2c => 3 + len,
+        }
+    }
+
+    /// Bus clock cycles to read `len` registers: 8 per byte for SPI, 9 per byte with the
+    /// acknowledge bit plus the start, repeated start and stop conditions for I2C.
+    pub fn read_cycles(&self, len: u32) -> u32 {
+        match self.kind {
+            TransportKind::Spi => 8 * self.read_bytes(len),
+            TransportKind::I2c => 9 * self.read_bytes(len) + 3,
+        }
+    }
+
+    /// Time in microseconds to read `len` registers in one transaction.
+    pub fn read_time_us(&self, len: u32) -> f32 {
+        self.read_cycles(len) as f32 * 1_000_000.0 / self.clock_hz as f32
+            + self.transaction_overhead_us as f32
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Bus usage of a way of reading the samples.
+pub struct ReadCost {
+    /// Bytes on the bus per second, framing included.
+    pub bytes_per_second: f32,
+    /// Fraction of the bus time used, the bus cannot keep up above 1.
+    pub utilization: f32,
+}
+
+impl ReadCost {
+    /// Cost of reading `samples` samples in `time_us` and `bytes` bytes, at `odr` Hz.
+    fn new(bytes: u32, time_us: f32, samples: u32, odr: f32) -> Self {
+        let batches_per_second = odr / samples as f32;
+        ReadCost {
+            bytes_per_second: bytes as f32 * batches_per_second,
+            utilization: time_us * batches_per_second / 1_000_000.0,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Bus throughput needed by a [`Config`] over a [`Transport`].
+pub struct BusBudget {
+    /// Reading each sample with `wait_data_ready` and `get_raw_accel_sample`: a STATUS read
+    /// and a 9-byte data read per sample.
+    pub polling: ReadCost,
+    /// Reading the FIFO in bursts of [`Self::watermark_samples`], or of
+    /// [`AcquisitionConfig::MAX_WATERMARK_SAMPLES`] when none is sustainable: STATUS and
+    /// FIFO_ENTRIES reads, then one FIFO_DATA transaction per sample.
+    pub fifo: ReadCost,
+    /// Recommended FIFO watermark, the largest keeping the bus utilization under
+    /// [`Self::MAX_UTILIZATION`] and the FIFO below the overrun point during the read.
+    /// `None` when no watermark does.
+    pub watermark_samples: Option<u8>,
+    /// Period at which to read the FIFO without the FIFO_FULL interrupt, the time to reach the
+    /// recommended watermark.
+    pub read_period_us: Option<u32>,
+}
+
+impl BusBudget {
+    /// Highest sustainable bus utilization, leaving a margin for the interrupt latency and the
+    /// other devices on the bus.
+    pub const MAX_UTILIZATION: f32 = 0.8;
+
+    /// Plan the bus throughput for the rate `config` outputs samples at with `sync` over
+    /// `transport`.
+    pub fn plan(config: &Config, sync: &ExternalSync, transport: Transport) -> Self {
+        let odr = sync.effective_odr(config.odr);
+        let period_us = sync.period_us(config.odr);
+
+        let polling = ReadCost::new(
+            transport.read_bytes(1) + transport.read_bytes(SAMPLE_BYTES),
+            transport.read_time_us(1) + transport.read_time_us(SAMPLE_BYTES),
+            1,
+            odr,
+        );
+
+        let mut watermark_samples = None;
+        for samples in (1..=AcquisitionConfig::MAX_WATERMARK_SAMPLES).rev() {
+            let (_, time_us) = Self::fifo_batch(&transport, samples.into());
+            let cost = ReadCost::new(0, time_us, samples.into(), odr);
+            // Samples keep coming in while the FIFO is read.
+            let arriving = (time_us / period_us) as u32 + 1;
+            if cost.utilization <= Self::MAX_UTILIZATION
+                && u32::from(samples) + arriving <= FIFO_CAPACITY_SAMPLES.into()
+            {
+                watermark_samples = Some(samples);
+                break;
+            }
+        }
+
+        let batch = watermark_samples
+            .unwrap_or(AcquisitionConfig::MAX_WATERMARK_SAMPLES)
+            .into();
+        let (bytes, time_us) = Self::fifo_batch(&transport, batch);
+        BusBudget {
+            polling,
+            fifo: ReadCost::new(bytes, time_us, batch, odr),
+            watermark_samples,
+            read_period_us: watermark_samples
+                .map(|samples| (f32::from(samples) * period_us) as u32),
+        }
+    }
+
+    /// Reading each sample is sustainable.
+    pub fn is_polling_sustainable(&self) -> bool {
+        self.polling.utilization <= Self::MAX_UTILIZATION
+    }
+
+    /// Reading the FIFO at the recommended watermark is sustainable.
+    pub fn is_sustainable(&self) -> bool {
+        self.watermark_samples.is_some()
+    }
+
+    /// Bytes and time to read `samples` samples from the FIFO as the drivers do.
+    fn fifo_batch(transport: &Transport, samples: u32) -> (u32, f32) {
+        // STATUS and FIFO_ENTRIES, then the x-axis entry and the rest of the first sample
+        // read apart, then one transaction per sample.
+        let lens = [1, 1, 3, SAMPLE_BYTES - 3];
+        let first_bytes: u32 = lens.iter().map(|&len| transport.read_bytes(len)).sum();
+        let first_time_us: f32 = lens.iter().map(|&len| transport.read_time_us(len)).sum();
+
+        let others = samples - 1;
+        (
+            first_bytes + others * transport.read_bytes(SAMPLE_BYTES),
+            first_time_us + others as f32 * transport.read_time_us(SAMPLE_BYTES),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{HpfCorner, Odr, Range};
+
+    fn plan(odr: Odr, transport: Transport) -> BusBudget {
+        let config = Config::new(Range::_2G, odr, HpfCorner::Off);
+        BusBudget::plan(&config, &ExternalSync::default(), transport)
+    }
+
+    #[test]
+    fn transport_framing() {
+        let spi = Transport::spi(1_000_000);
+        assert_eq!(spi.read_bytes(9), 10);
+        assert_eq!(spi.read_cycles(9), 80);
+        assert_eq!(spi.read_time_us(9), 80.0);
+
+        let mut i2c = Transport::i2c(1_000_000);
+        assert_eq!(i2c.read_bytes(9), 12);
+        assert_eq!(i2c.read_cycles(9), 111);
+        i2c.transaction_overhead_us(10);
+        assert_eq!(i2c.read_time_us(9), 121.0);
+    }
+
+    #[test]
+    fn slow_i2c_cannot_keep_up_with_4khz() {
+        let budget = plan(Odr::_4000Hz, Transport::i2c(100_000));
+        assert!(!budget.is_polling_sustainable());
+        assert!(!budget.is_sustainable());
+        assert_eq!(budget.watermark_samples, None);
+        assert_eq!(budget.read_period_us, None);
+        assert!(budget.polling.utilization > 5.0);
+        assert!(budget.fifo.utilization > 1.0);
+    }
+
+    #[test]
+    fn fast_spi_reads_the_largest_watermark() {
+        let budget = plan(Odr::_4000Hz, Transport::spi(10_000_000));
+        assert!(budget.is_polling_sustainable());
+        assert!(budget.is_sustainable());
+        assert_eq!(
+            budget.watermark_samples,
+            Some(AcquisitionConfig::MAX_WATERMARK_SAMPLES)
+        );
+        assert_eq!(budget.read_period_us, Some(6_000));
+        // STATUS, FIFO_ENTRIES, the first sample in 2 reads and 23 samples, at 4000 / 24 Hz.
+        let bytes = 2 * 2 + 4 + 7 + 23 * 10;
+        let bytes_per_second = bytes as f32 * 4000.0 / 24.0;
+        assert!((budget.fifo.bytes_per_second - bytes_per_second).abs() < 0.01);
+        assert!(budget.fifo.utilization < budget.polling.utilization);
+    }
+
+    #[test]
+    fn transaction_overhead_lowers_the_watermark() {
+        let mut spi = Transport::spi(10_000_000);
+        spi.transaction_overhead_us(200);
+        let budget = plan(Odr::_4000Hz, spi);
+        assert!(!budget.is_polling_sustainable());
+        assert!(!budget.is_sustainable());
+
+        let budget = plan(Odr::_2000Hz, spi);
+        let samples = budget.watermark_samples.unwrap();
+        assert!(samples < AcquisitionConfig::MAX_WATERMARK_SAMPLES);
+        assert!(budget.fifo.utilization <= BusBudget::MAX_UTILIZATION);
+        assert_eq!(budget.read_period_us, Some(u32::from(samples) * 500));
+    }
+
+    #[test]
+    fn slow_odr_is_sustainable_on_slow_i2c() {
+        let budget = plan(Odr::_3_906Hz, Transport::i2c(100_000));
+        assert!(budget.is_polling_sustainable());
+        assert!(budget.is_sustainable());
+    }
+}
@@ -12,20 +12,46 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-#[derive(Copy, Clone, Debug, Default)]
+use core::fmt;
+use core::str::FromStr;
+
+use crate::planner::FilterResponse;
+use crate::regs::{self, Filter};
+use crate::sync::ExternalSync;
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Accelerometer range. The ADXL355 supports the ±2 g, ±4 g, and ±8 g ranges.
+/// The ADXL357 and ADXL359 support the ±10.24 g, ±20.48 g, and ±40.96 g ranges,
+/// see [`crate::variant::Variant`].
+///
+/// The ADXL355 ranges have the value of their range bits as discriminant, the ADXL357 and ADXL359
+/// ranges reuse the same range bits and are mapped through [`Self::val`].
 pub enum Range {
     #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "2g"))]
     _2G = 0b01,
+    #[cfg_attr(feature = "serde", serde(rename = "4g"))]
     _4G = 0b10,
+    #[cfg_attr(feature = "serde", serde(rename = "8g"))]
     _8G = 0b11,
+    #[cfg_attr(feature = "serde", serde(rename = "10.24g"))]
+    _10G,
+    #[cfg_attr(feature = "serde", serde(rename = "20.48g"))]
+    _20G,
+    #[cfg_attr(feature = "serde", serde(rename = "40.96g"))]
+    _40G,
 }
 
 impl Range {
     /// Range configuration value as expected in the Range bits of the [`crate::register::Register::RANGE`] register.
     pub const fn val(self) -> u8 {
-        self as u8
+        match self {
+            Range::_2G | Range::_10G => 0b01,
+            Range::_4G | Range::_20G => 0b10,
+            Range::_8G | Range::_40G => 0b11,
+        }
     }
 
     /// Range scale factor to go from the accelerometer provided integer raw value to g value.
@@ -34,14 +60,119 @@ impl Range {
             Range::_2G => 0.000_003_9,
             Range::_4G => 0.000_007_8,
             Range::_8G => 0.000_015_6,
+            Range::_10G => 0.000_019_5,
+            Range::_20G => 0.000_039,
+            Range::_40G => 0.000_078,
+        }
+    }
+
+    /// Typical noise density in µg/√Hz: 22.5 for the ADXL355 ranges, 80 for the ADXL357 and
+    /// ADXL359 ranges.
+    pub const fn noise_density_ug(self) -> f32 {
+        match self {
+            Range::_2G | Range::_4G | Range::_8G => 22.5,
+            Range::_10G | Range::_20G | Range::_40G => 80.0,
+        }
+    }
+
+    /// Exact scale factor from the raw value to micro-g, matching [`Self::scale_factor`].
+    pub const fn micro_g_per_lsb(self) -> ScaleRatio {
+        match self {
+            Range::_2G => ScaleRatio::from_parts(39, 10),
+            Range::_4G => ScaleRatio::from_parts(78, 10),
+            Range::_8G => ScaleRatio::from_parts(156, 10),
+            Range::_10G => ScaleRatio::from_parts(195, 10),
+            Range::_20G => ScaleRatio::from_parts(390, 10),
+            Range::_40G => ScaleRatio::from_parts(780, 10),
+        }
+    }
+
+    /// Exact scale factor from the raw value to milli-m/s², with the standard gravity.
+    pub const fn milli_mps2_per_lsb(self) -> ScaleRatio {
+        let micro_g = self.micro_g_per_lsb();
+        ScaleRatio::from_parts(
+            micro_g.numerator * ScaleRatio::MICRO_G_TO_MILLI_MPS2.numerator,
+            micro_g.denominator * ScaleRatio::MICRO_G_TO_MILLI_MPS2.denominator,
+        )
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Rational scale factor `numerator / denominator`, to convert values with integer arithmetic
+/// only on targets without a floating point unit.
+pub struct ScaleRatio {
+    /// Multiplier of the scaled value.
+    numerator: u32,
+    /// Divisor of the scaled value, never zero.
+    denominator: u32,
+}
+
+impl ScaleRatio {
+    /// Micro-g to milli-m/s², with the standard gravity of 9.80665 m/s².
+    pub const MICRO_G_TO_MILLI_MPS2: ScaleRatio = ScaleRatio::from_parts(980_665, 100_000_000);
+
+    /// Raw temperature change to milli-degrees Celsius, the temperature slope being
+    /// −9.05 LSB/°C.
+    pub const TEMPERATURE_MILLI_CELSIUS_PER_LSB: ScaleRatio = ScaleRatio::from_parts(100_000, 905);
+
+    /// Creates the ratio `numerator / denominator`, `None` when `denominator` is zero.
+    pub const fn new(numerator: u32, denominator: u32) -> Option<Self> {
+        if denominator == 0 {
+            None
+        } else {
+            Some(ScaleRatio {
+                numerator,
+                denominator,
+            })
+        }
+    }
+
+    /// Creates a ratio from a non-zero `denominator`, for the constants of the crate.
+    const fn from_parts(numerator: u32, denominator: u32) -> Self {
+        ScaleRatio {
+            numerator,
+            denominator,
+        }
+    }
+
+    /// Multiplier of the scaled value.
+    pub const fn numerator(self) -> u32 {
+        self.numerator
+    }
+
+    /// Divisor of the scaled value, never zero.
+    pub const fn denominator(self) -> u32 {
+        self.denominator
+    }
+
+    /// Scale `value`, rounded to the nearest integer.
+    /// The result saturates when it does not fit an `i32`.
+    pub const fn scale(self, value: i32) -> i32 {
+        let product = value as i64 * self.numerator as i64;
+        let denominator = self.denominator as i64;
+        let half = denominator / 2;
+        let scaled = if product < 0 {
+            (product - half) / denominator
+        } else {
+            (product + half) / denominator
+        };
+        if scaled > i32::MAX as i64 {
+            i32::MAX
+        } else if scaled < i32::MIN as i64 {
+            i32::MIN
+        } else {
+            scaled as i32
         }
     }
 }
 
 /// Enables retrieving Range enum value from the [`crate::register::Register::RANGE`] register value.
+/// The value is decoded as an ADXL355 range, use [`crate::variant::Variant::range_from_register`]
+/// for other variants.
 impl From<u8> for Range {
     fn from(value: u8) -> Self {
-        match value & 0x03 {
+        match value & regs::Range::RANGE_BITS {
             1 => Range::_2G,
             2 => Range::_4G,
             3 => Range::_8G,
@@ -52,35 +183,68 @@ impl From<u8> for Range {
 
 #[derive(Copy, Clone, Debug, Default)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Accelerometer's output data rate (ODR).
 /// The low pass filter corner frequency (LPF) is configured cojointly, the LPF is 1/4th the ODR.
 pub enum Odr {
     /// odr = 4000 Hz and lpf = 1000 Hz
+    #[cfg_attr(feature = "serde", serde(rename = "4000Hz"))]
     _4000Hz = 0,
     /// odr = 2000 Hz and lpf = 500 Hz
+    #[cfg_attr(feature = "serde", serde(rename = "2000Hz"))]
     _2000Hz = 1,
     /// odr = 1000 Hz and lpf = 250 Hz
+    #[cfg_attr(feature = "serde", serde(rename = "1000Hz"))]
     _1000Hz = 2,
     /// odr = 500 Hz and lpf = 125 Hz
+    #[cfg_attr(feature = "serde", serde(rename = "500Hz"))]
     _500Hz = 3,
     /// odr = 250 Hz and lpf = 62.5 Hz
+    #[cfg_attr(feature = "serde", serde(rename = "250Hz"))]
     _250Hz = 4,
     /// odr = 125 Hz and lpf = 31.25 Hz
+    #[cfg_attr(feature = "serde", serde(rename = "125Hz"))]
     _125Hz = 5,
     /// odr = 62.5 Hz and lpf = 15.625 Hz
+    #[cfg_attr(feature = "serde", serde(rename = "62.5Hz"))]
     _62_5Hz = 6,
     /// odr = 31.25 Hz and lpf = 7.813 Hz
+    #[cfg_attr(feature = "serde", serde(rename = "31.25Hz"))]
     _31_25Hz = 7,
     /// odr = 15.625 Hz and lpf = 3.906
+    #[cfg_attr(feature = "serde", serde(rename = "15.625Hz"))]
     _15_625Hz = 8,
     /// odr = 7.813 Hz and lpf = 1.953 Hz
+    #[cfg_attr(feature = "serde", serde(rename = "7.813Hz"))]
     _7_813Hz = 9,
     #[default]
     /// odr = 3.906 Hz and lpf = 0.977 Hz
+    #[cfg_attr(feature = "serde", serde(rename = "3.906Hz"))]
     _3_906Hz = 10,
 }
 
 impl Odr {
+    /// Every output data rate, from the fastest to the slowest.
+    pub const ALL: [Odr; 11] = [
+        Odr::_4000Hz,
+        Odr::_2000Hz,
+        Odr::_1000Hz,
+        Odr::_500Hz,
+        Odr::_250Hz,
+        Odr::_125Hz,
+        Odr::_62_5Hz,
+        Odr::_31_25Hz,
+        Odr::_15_625Hz,
+        Odr::_7_813Hz,
+        Odr::_3_906Hz,
+    ];
+
+    /// Number of output periods waited for the decimation and low-pass filters to settle after
+    /// entering measurement mode or changing the ODR. The datasheet gives their group delay per
+    /// ODR in its digital filtering section; this value is a margin over it, not a datasheet
+    /// figure, and is kept small next to the high-pass filter settling time.
+    pub const LPF_SETTLING_PERIODS: u32 = 16;
+
     /// ODR_LPF configuration value as expected in the ODR_LPF bits of the [`crate::register::Register::FILTER`] register.
     pub const fn val(self) -> u8 {
         self as u8
@@ -119,12 +283,17 @@ impl Odr {
             Odr::_3_906Hz => 0.977,
         }
     }
+
+    /// Output period in microseconds, the ODR halves from 4000 Hz at each step.
+    pub const fn period_us(self) -> u32 {
+        250 << self.val()
+    }
 }
 
 /// Enables retrieving ODR_LPF enum value from the [`crate::register::Register::FILTER`] register value.
 impl From<u8> for Odr {
     fn from(value: u8) -> Self {
-        match value & 0x0F {
+        match value & Filter::ODR_LPF_BITS {
             0 => Odr::_4000Hz,
             1 => Odr::_2000Hz,
             2 => Odr::_1000Hz,
@@ -143,27 +312,49 @@ impl From<u8> for Odr {
 
 #[derive(Copy, Clone, Debug, Default)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Accelerometer's −3 dB filter corner for the first-order.
 /// The ADXL355 offers 7 different configurations, the high-pass filter frequency is relative to the ODR.
 pub enum HpfCorner {
     /// Not applicable, no high-pass filter enabled
     #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "off"))]
     Off = 0,
     /// 24.7 × 10^-4 × ODR
+    #[cfg_attr(feature = "serde", serde(rename = "24.7"))]
     _24_7 = 1,
     /// 6.2048 × 10^-4 × ODR
+    #[cfg_attr(feature = "serde", serde(rename = "6.2084"))]
     _6_2084 = 2,
     /// 1.5454 × 10^-4 × ODR
+    #[cfg_attr(feature = "serde", serde(rename = "1.5545"))]
     _1_5545 = 3,
     /// 0.3862 × 10^-4 × ODR
+    #[cfg_attr(feature = "serde", serde(rename = "0.3862"))]
     _0_3862 = 4,
     /// 0.0954 × 10^-4 × ODR
+    #[cfg_attr(feature = "serde", serde(rename = "0.0954"))]
     _0_0954 = 5,
     /// 0.0238 × 10^-4 × ODR
+    #[cfg_attr(feature = "serde", serde(rename = "0.0238"))]
     _0_0238 = 6,
 }
 
 impl HpfCorner {
+    /// Every high-pass filter setting, from off to the lowest corner.
+    pub const ALL: [HpfCorner; 7] = [
+        HpfCorner::Off,
+        HpfCorner::_24_7,
+        HpfCorner::_6_2084,
+        HpfCorner::_1_5545,
+        HpfCorner::_0_3862,
+        HpfCorner::_0_0954,
+        HpfCorner::_0_0238,
+    ];
+
+    /// Number of time constants for the first-order high-pass filter to settle within 0.1 %.
+    pub const SETTLING_TIME_CONSTANTS: f32 = 7.0;
+
     /// HPF_CORNER configuration value as expected in the HPF_CORNER bits of the [`crate::register::Register::FILTER`] register.
     pub const fn val(self) -> u8 {
         self as u8
@@ -182,12 +373,32 @@ impl HpfCorner {
             HpfCorner::_0_0238 => Some(0.0238e-4),
         }
     }
+
+    /// High-pass filter −3 dB corner in Hertz at the given output data rate, `None` when the
+    /// filter is off.
+    pub fn corner_hz(self, odr: Odr) -> Option<f32> {
+        self.hpf_coefficient()
+            .map(|coefficient| coefficient * odr.odr())
+    }
+
+    /// Number of output periods for the high-pass filter to settle, 0 when it is off.
+    /// The corner being relative to the ODR, it does not depend on the ODR.
+    pub fn settling_periods(self) -> u32 {
+        match self.hpf_coefficient() {
+            // time constant in periods: 1 / (2π × coefficient)
+            Some(coefficient) => {
+                (Self::SETTLING_TIME_CONSTANTS / (2.0 * core::f32::consts::PI * coefficient)) as u32
+                    + 1
+            }
+            None => 0,
+        }
+    }
 }
 
 /// Enables retrieving HPF_CORNER enum value from the [`crate::register::Register::FILTER`] register value.
 impl From<u8> for HpfCorner {
     fn from(value: u8) -> Self {
-        match (value & 0x70) >> 4 {
+        match (value & Filter::HPF_CORNER_BITS) >> Filter::HPF_CORNER_SHIFT {
             0 => HpfCorner::Off,
             1 => HpfCorner::_24_7,
             2 => HpfCorner::_6_2084,
@@ -202,6 +413,7 @@ impl From<u8> for HpfCorner {
 
 #[derive(Copy, Clone, Debug, Default)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Configuration for the ADXL355.
 /// The default configuration [`Self::default()`] uses:
 ///
@@ -237,4 +449,322 @@ impl Config {
         self.hpf = hpf;
         self
     }
+
+    /// Number of samples to discard after entering measurement mode for the low-pass and
+    /// high-pass filters to settle.
+    pub fn settling_samples(&self) -> u32 {
+        Odr::LPF_SETTLING_PERIODS + self.hpf.settling_periods()
+    }
+
+    /// Time in microseconds for the filters to settle after entering measurement mode.
+    /// With the lowest high-pass corners this amounts to minutes, or hours at low ODRs.
+    pub fn settling_time_us(&self) -> u64 {
+        u64::from(self.settling_samples()) * u64::from(self.odr.period_us())
+    }
+
+    /// Filter corners in Hertz, noise density and settling time of this configuration on the
+    /// internal clock, see [`FilterResponse::new`] for an external clock or synchronization.
+    pub fn describe(&self) -> FilterResponse {
+        FilterResponse::new(*self, &ExternalSync::default())
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
+/// Error parsing a [`Range`], an [`Odr`], a [`HpfCorner`] or a [`Config`] from text.
+pub enum ParseError {
+    /// Not one of the ranges, e.g. `4g`.
+    #[cfg_attr(
+        feature = "std",
+        error("invalid range, expected 2g, 4g, 8g, 10.24g, 20.48g or 40.96g")
+    )]
+    Range,
+    /// Not one of the output data rates, e.g. `500Hz`.
+    #[cfg_attr(
+        feature = "std",
+        error(
+            "invalid output data rate, expected 4000Hz, 2000Hz, 1000Hz, 500Hz, 250Hz, 125Hz, \
+             62.5Hz, 31.25Hz, 15.625Hz, 7.813Hz or 3.906Hz"
+        )
+    )]
+    Odr,
+    /// Not one of the high-pass filter corners, e.g. `0.3862` or `off`.
+    #[cfg_attr(
+        feature = "std",
+        error(
+            "invalid high-pass corner, expected off or a coefficient of the ODR in 10^-4 among \
+             24.7, 6.2084, 1.5545, 0.3862, 0.0954 and 0.0238"
+        )
+    )]
+    HpfCorner,
+    /// A [`Config`] entry is not of the form `key=value`.
+    #[cfg_attr(feature = "std", error("invalid config entry, expected key=value"))]
+    Entry,
+    /// A [`Config`] key is not `range`, `odr` or `hpf`.
+    #[cfg_attr(
+        feature = "std",
+        error("unknown config key, expected range, odr or hpf")
+    )]
+    Key,
+}
+
+/// Formats the range as `4g`, `10.24g`.
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let g = match self {
+            Range::_2G => "2",
+            Range::_4G => "4",
+            Range::_8G => "8",
+            Range::_10G => "10.24",
+            Range::_20G => "20.48",
+            Range::_40G => "40.96",
+        };
+        write!(f, "{g}g")
+    }
+}
+
+/// Parses a range such as `4g`, `±4 g` or `10.24g`, the ADXL357 and ADXL359 ranges can also be
+/// written `10g`, `20g` and `40g`.
+impl FromStr for Range {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s.strip_prefix('±').unwrap_or(s);
+        let g = strip_suffix_ignore_case(s, "g").ok_or(ParseError::Range)?;
+        match g.trim_end() {
+            "2" => Ok(Range::_2G),
+            "4" => Ok(Range::_4G),
+            "8" => Ok(Range::_8G),
+            "10" | "10.24" => Ok(Range::_10G),
+            "20" | "20.48" => Ok(Range::_20G),
+            "40" | "40.96" => Ok(Range::_40G),
+            _ => Err(ParseError::Range),
+        }
+    }
+}
+
+/// Formats the output data rate as `500Hz`, `62.5Hz`.
+impl fmt::Display for Odr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}Hz", self.odr())
+    }
+}
+
+/// Parses an output data rate such as `500Hz` or `62.5 Hz`, within 0.1 % of the nominal rate
+/// so that `7.8125Hz` is accepted as well.
+impl FromStr for Odr {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hz = strip_suffix_ignore_case(s.trim(), "hz").ok_or(ParseError::Odr)?;
+        let hz: f32 = hz.trim_end().parse().map_err(|_| ParseError::Odr)?;
+        Odr::ALL
+            .into_iter()
+            .find(|odr| approx_eq(hz, odr.odr()))
+            .ok_or(ParseError::Odr)
+    }
+}
+
+/// Formats the high-pass corner as its coefficient of the ODR in 10^-4, such as `0.3862`, or
+/// `off`.
+impl fmt::Display for HpfCorner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let coefficient = match self {
+            HpfCorner::Off => "off",
+            HpfCorner::_24_7 => "24.7",
+            HpfCorner::_6_2084 => "6.2084",
+            HpfCorner::_1_5545 => "1.5545",
+            HpfCorner::_0_3862 => "0.3862",
+            HpfCorner::_0_0954 => "0.0954",
+            HpfCorner::_0_0238 => "0.0238",
+        };
+        f.write_str(coefficient)
+    }
+}
+
+/// Parses a high-pass corner such as `0.3862`, `hpf=0.3862` or `off`, the coefficient of the ODR
+/// in 10^-4 being accepted within 0.1 %.
+impl FromStr for HpfCorner {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s.strip_prefix("hpf=").unwrap_or(s);
+        if s.eq_ignore_ascii_case("off") {
+            return Ok(HpfCorner::Off);
+        }
+        let coefficient: f32 = s.parse().map_err(|_| ParseError::HpfCorner)?;
+        HpfCorner::ALL
+            .into_iter()
+            .find(|hpf| {
+                hpf.hpf_coefficient()
+                    .map_or(false, |nominal| approx_eq(coefficient * 1e-4, nominal))
+            })
+            .ok_or(ParseError::HpfCorner)
+    }
+}
+
+/// Formats the configuration as `range=4g odr=500Hz hpf=0.3862`.
+impl fmt::Display for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "range={} odr={} hpf={}", self.range, self.odr, self.hpf)
+    }
+}
+
+/// Parses a configuration such as `range=4g odr=500Hz hpf=0.3862`, the entries being separated
+/// by spaces or commas. Missing entries keep their default value.
+impl FromStr for Config {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut config = Config::default();
+        let entries = s
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|entry| !entry.is_empty());
+        for entry in entries {
+            let (key, value) = entry.split_once('=').ok_or(ParseError::Entry)?;
+            match key {
+                "range" => config.range = value.parse()?,
+                "odr" => config.odr = value.parse()?,
+                "hpf" => config.hpf = value.parse()?,
+                _ => return Err(ParseError::Key),
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// Strip `suffix` from the end of `s`, ignoring the ASCII case.
+fn strip_suffix_ignore_case<'a>(s: &'a str, suffix: &str) -> Option<&'a str> {
+    let split = s.len().checked_sub(suffix.len())?;
+    (s.is_char_boundary(split) && s[split..].eq_ignore_ascii_case(suffix)).then(|| &s[..split])
+}
+
+/// `value` is within 0.1 % of `nominal`.
+fn approx_eq(value: f32, nominal: f32) -> bool {
+    let tolerance = nominal * 1e-3;
+    value >= nominal - tolerance && value <= nominal + tolerance
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::ToString;
+
+    use super::*;
+
+    const RANGES: [Range; 6] = [
+        Range::_2G,
+        Range::_4G,
+        Range::_8G,
+        Range::_10G,
+        Range::_20G,
+        Range::_40G,
+    ];
+
+    #[test]
+    fn text_round_trips() {
+        for range in RANGES {
+            assert_eq!(range.to_string().parse(), Ok(range));
+        }
+        for odr in Odr::ALL {
+            assert_eq!(odr.to_string().parse::<Odr>().map(Odr::val), Ok(odr.val()));
+        }
+        for hpf in HpfCorner::ALL {
+            let parsed = hpf.to_string().parse::<HpfCorner>();
+            assert_eq!(parsed.map(HpfCorner::val), Ok(hpf.val()));
+        }
+
+        let config = Config::new(Range::_4G, Odr::_500Hz, HpfCorner::_0_3862);
+        let text = config.to_string();
+        assert_eq!(text, "range=4g odr=500Hz hpf=0.3862");
+        let parsed: Config = text.parse().unwrap();
+        assert_eq!(parsed.range, config.range);
+        assert_eq!(parsed.odr.val(), config.odr.val());
+        assert_eq!(parsed.hpf.val(), config.hpf.val());
+    }
+
+    #[test]
+    fn text_accepts_alternative_forms() {
+        assert_eq!("±4 g".parse(), Ok(Range::_4G));
+        assert_eq!("10G".parse(), Ok(Range::_10G));
+        assert_eq!("40g".parse(), Ok(Range::_40G));
+        assert_eq!(
+            "62.5 hz".parse::<Odr>().map(Odr::val),
+            Ok(Odr::_62_5Hz.val())
+        );
+        assert_eq!(
+            "7.8125Hz".parse::<Odr>().map(Odr::val),
+            Ok(Odr::_7_813Hz.val())
+        );
+        assert_eq!(
+            "hpf=0.3862".parse::<HpfCorner>().map(HpfCorner::val),
+            Ok(HpfCorner::_0_3862.val())
+        );
+        assert_eq!(
+            "OFF".parse::<HpfCorner>().map(HpfCorner::val),
+            Ok(HpfCorner::Off.val())
+        );
+
+        let config: Config = "odr=4000Hz,range=8g".parse().unwrap();
+        assert_eq!(config.range, Range::_8G);
+        assert_eq!(config.odr.val(), Odr::_4000Hz.val());
+        assert_eq!(config.hpf.val(), HpfCorner::default().val());
+    }
+
+    #[test]
+    fn text_rejects_invalid_values() {
+        assert_eq!("3g".parse::<Range>(), Err(ParseError::Range));
+        assert_eq!("4".parse::<Range>(), Err(ParseError::Range));
+        assert_eq!("é".parse::<Range>(), Err(ParseError::Range));
+        assert_eq!("501Hz".parse::<Odr>().err(), Some(ParseError::Odr));
+        assert_eq!("500".parse::<Odr>().err(), Some(ParseError::Odr));
+        assert_eq!("1".parse::<HpfCorner>().err(), Some(ParseError::HpfCorner));
+        assert_eq!("range".parse::<Config>().err(), Some(ParseError::Entry));
+        assert_eq!(
+            "range=4g foo=1".parse::<Config>().err(),
+            Some(ParseError::Key)
+        );
+        assert_eq!(
+            "range=4g odr=1Hz".parse::<Config>().err(),
+            Some(ParseError::Odr)
+        );
+    }
+
+    #[test]
+    fn scale_ratio_rounds_to_nearest() {
+        let ratio = ScaleRatio::new(39, 10).unwrap();
+        assert_eq!(ratio, Range::_2G.micro_g_per_lsb());
+        assert_eq!(ratio.scale(0), 0);
+        assert_eq!(ratio.scale(1), 4);
+        assert_eq!(ratio.scale(-1), -4);
+        assert_eq!(ratio.scale(3), 12);
+        assert_eq!(ratio.scale(-3), -12);
+        // Halves round away from zero.
+        assert_eq!(ratio.scale(5), 20);
+        assert_eq!(ratio.scale(-5), -20);
+    }
+
+    #[test]
+    fn scale_ratio_converts_the_full_scale() {
+        assert_eq!(Range::_2G.micro_g_per_lsb().scale(256_000), 998_400);
+        assert_eq!(Range::_40G.micro_g_per_lsb().scale(-0x80000), -40_894_464);
+        assert_eq!(ScaleRatio::MICRO_G_TO_MILLI_MPS2.scale(1_000_000), 9_807);
+        assert_eq!(Range::_2G.milli_mps2_per_lsb().scale(256_000), 9_791);
+        let ratio = Range::_40G.milli_mps2_per_lsb();
+        assert_eq!(ratio.numerator(), 780 * 980_665);
+        assert_eq!(ratio.denominator(), 10 * 100_000_000);
+    }
+
+    #[test]
+    fn scale_ratio_saturates() {
+        let ratio = ScaleRatio::new(u32::MAX, 1).unwrap();
+        assert_eq!(ratio.scale(2), i32::MAX);
+        assert_eq!(ratio.scale(-2), i32::MIN);
+        assert_eq!(ScaleRatio::new(1, u32::MAX).unwrap().scale(i32::MAX), 0);
+        assert_eq!(ScaleRatio::new(1, 0), None);
+    }
 }
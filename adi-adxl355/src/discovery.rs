@@ -0,0 +1,96 @@
+// Copyright © 2023 Analog Devices, Inc. All Rights Reserved. This software is
+// proprietary to Analog Devices, Inc. and its licensors.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use embedded_hal::i2c::{self, I2c};
+
+use crate::identity::DeviceInfo;
+use crate::register::Register;
+use crate::variant::Variant;
+use crate::{DeviceI2cAddress, Error};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Sensor found on an I2C bus.
+pub struct DiscoveredDevice {
+    /// I2C address the sensor answered on.
+    pub address: DeviceI2cAddress,
+    /// Identification registers read from the sensor.
+    pub info: DeviceInfo,
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Sensors found on an I2C bus, at most one per ASEL address.
+///
+/// The drivers can then be created with `new_i2c_with_variant` using the address of each
+/// sensor, over a shared bus implementation (such as the ones from `embedded-hal-bus`)
+/// when several sensors were found.
+pub struct I2cDiscovery {
+    /// Sensor found for each of [`DeviceI2cAddress::ALL`], in the same order.
+    pub devices: [Option<DiscoveredDevice>; 2],
+}
+
+impl I2cDiscovery {
+    /// Iterate over the sensors found.
+    pub fn iter(&self) -> impl Iterator<Item = &DiscoveredDevice> {
+        self.devices.iter().flatten()
+    }
+
+    /// Number of sensors found.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Check if no sensor was found.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Check if an I2C error means no device answered at the probed address.
+pub fn is_device_absent(kind: i2c::ErrorKind) -> bool {
+    matches!(kind, i2c::ErrorKind::NoAcknowledge(_))
+}
+
+/// Probe both ASEL addresses on the I2C bus and return the sensors of the given [`Variant`] found.
+///
+/// An address which is not acknowledged is considered empty, a device answering with other
+/// identification registers than the expected ones is ignored. The bus is only borrowed, it
+/// can be used to create the drivers afterwards.
+pub fn discover_i2c<I2C, VARIANT>(
+    i2c: &mut I2C,
+    _variant: VARIANT,
+) -> Result<I2cDiscovery, Error<I2C::Error>>
+where
+    I2C: I2c,
+    VARIANT: Variant,
+{
+    let mut discovery = I2cDiscovery::default();
+
+    for (slot, address) in discovery.devices.iter_mut().zip(DeviceI2cAddress::ALL) {
+        let mut buf = [0u8; 4];
+        match i2c.write_read(address as u8, &[Register::DEVID_AD.addr()], &mut buf) {
+            Ok(()) => {
+                let info = DeviceInfo::from_registers(buf);
+                if info.check::<VARIANT>().is_ok() {
+                    *slot = Some(DiscoveredDevice { address, info });
+                }
+            }
+            Err(e) if is_device_absent(i2c::Error::kind(&e)) => {}
+            Err(e) => return Err(Error::Protocol(e)),
+        }
+    }
+
+    Ok(discovery)
+}
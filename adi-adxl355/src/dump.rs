@@ -0,0 +1,419 @@
+// Copyright © 2023 Analog Devices, Inc. All Rights Reserved. This software is
+// proprietary to Analog Devices, Inc. and its licensors.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+
+use crate::config::{Config, Range};
+use crate::modes::Mode;
+use crate::register::Register;
+use crate::regs::{
+    self, ActCount, ActEn, ActThresh, ExtSync, FifoEntries, FifoSamples, Filter, OffsetX, OffsetY,
+    OffsetZ, RegisterBits, RegisterValue, SelfTest, Temperature,
+};
+use crate::sans_io::{self, RegisterOp};
+use crate::status::Status;
+use crate::variant::Variant;
+
+/// Values of the control registers after a reset.
+pub const RESET_VALUES: [(Register, u8); 18] = [
+    (Register::OFFSET_X_H, 0x00),
+    (Register::OFFSET_X_L, 0x00),
+    (Register::OFFSET_Y_H, 0x00),
+    (Register::OFFSET_Y_L, 0x00),
+    (Register::OFFSET_Z_H, 0x00),
+    (Register::OFFSET_Z_L, 0x00),
+    (Register::ACT_EN, 0x00),
+    (Register::ACT_THRESH_H, 0x00),
+    (Register::ACT_THRESH_L, 0x00),
+    (Register::ACT_COUNT, 0x01),
+    (Register::FILTER, 0x00),
+    (Register::FIFO_SAMPLES, 0x60),
+    (Register::INT_MAP, 0x00),
+    (Register::SYNC, 0x00),
+    (Register::RANGE, 0x81),
+    (Register::POWER_CTL, 0x01),
+    (Register::SELF_TEST, 0x00),
+    (Register::RESET, 0x00),
+];
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Register holding a different value than expected.
+pub struct RegisterDiff {
+    /// Register compared.
+    pub register: Register,
+    /// Value the register should hold.
+    pub expected: u8,
+    /// Value read from the register.
+    pub actual: u8,
+}
+
+impl fmt::Display for RegisterDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?}: expected 0x{:02X}, got 0x{:02X}",
+            self.register, self.expected, self.actual
+        )
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Values of every register of the device but [`Register::FIFO_DATA`], which would pop an
+/// entry from the FIFO when read.
+///
+/// The [`fmt::Display`] implementation prints one register per line with its decoded fields.
+pub struct RegisterDump {
+    status_and_data: [u8; 17],
+    control: [u8; 18],
+    shadow: [u8; 5],
+    ranges: [Range; 3],
+}
+
+impl RegisterDump {
+    /// Create a dump of a [`Variant`] from the bursts read at
+    /// [`crate::register::RegisterBlock::STATUS_AND_DATA`],
+    /// [`crate::register::RegisterBlock::CONTROL`] and
+    /// [`crate::register::RegisterBlock::SHADOW`].
+    pub fn new<VARIANT: Variant>(
+        status_and_data: [u8; 17],
+        control: [u8; 18],
+        shadow: [u8; 5],
+    ) -> Self {
+        RegisterDump {
+            status_and_data,
+            control,
+            shadow,
+            ranges: VARIANT::RANGES,
+        }
+    }
+
+    /// Get the value of a register, `None` for [`Register::FIFO_DATA`].
+    pub fn get(&self, register: Register) -> Option<u8> {
+        let addr = register.addr() as usize;
+        match register.addr() {
+            0x00..=0x10 => Some(self.status_and_data[addr]),
+            0x1E..=0x2F => Some(self.control[addr - 0x1E]),
+            0x50..=0x54 => Some(self.shadow[addr - 0x50]),
+            _ => None,
+        }
+    }
+
+    /// Get the typed value of a register, see [`regs`].
+    pub fn value<T: RegisterValue>(&self) -> T {
+        let mut buf = [0u8; 2];
+        let start = T::REGISTER.addr();
+        for (offset, byte) in buf[..T::Bits::LEN].iter_mut().enumerate() {
+            let register = Register::try_from(start + offset as u8);
+            *byte = register.ok().and_then(|r| self.get(r)).unwrap_or(0);
+        }
+        T::from_bits(T::Bits::from_bytes(&buf))
+    }
+
+    /// Iterate over the registers and their values, in address order.
+    pub fn iter(&self) -> impl Iterator<Item = (Register, u8)> + '_ {
+        (0x00..=0x10u8)
+            .chain(0x1E..=0x2F)
+            .chain(0x50..=0x54)
+            .filter_map(|addr| Register::try_from(addr).ok())
+            .filter_map(|register| self.get(register).map(|value| (register, value)))
+    }
+
+    /// List the registers holding a different value than in the `reference` dump.
+    /// The [`Register::volatile`] registers are ignored.
+    pub fn diff<'a>(
+        &'a self,
+        reference: &'a RegisterDump,
+    ) -> impl Iterator<Item = RegisterDiff> + 'a {
+        self.iter()
+            .zip(reference.iter())
+            .filter(|((register, _), _)| !register.volatile())
+            .filter(|((_, actual), (_, expected))| actual != expected)
+            .map(|((register, actual), (_, expected))| RegisterDiff {
+                register,
+                expected,
+                actual,
+            })
+    }
+
+    /// List the control registers holding a different value than after a reset,
+    /// see [`RESET_VALUES`].
+    pub fn diff_reset(&self) -> impl Iterator<Item = RegisterDiff> + '_ {
+        RESET_VALUES.iter().filter_map(|&(register, expected)| {
+            let actual = self.get(register)?;
+            (actual != expected).then_some(RegisterDiff {
+                register,
+                expected,
+                actual,
+            })
+        })
+    }
+
+    /// List the registers that do not hold the [`Config`]. Only the configured bits are
+    /// compared.
+    pub fn diff_config(&self, config: &Config) -> impl Iterator<Item = RegisterDiff> + '_ {
+        sans_io::config_ops(config).into_iter().filter_map(|op| {
+            let (register, mask, value) = match op {
+                RegisterOp::Write(register, value) => (register, 0xFF, value),
+                RegisterOp::Update {
+                    register,
+                    mask,
+                    value,
+                } => (register, mask, value),
+            };
+            let actual = self.get(register)?;
+            let expected = RegisterOp::updated_value(mask, value, actual);
+            (actual != expected).then_some(RegisterDiff {
+                register,
+                expected,
+                actual,
+            })
+        })
+    }
+
+    /// Write the decoded fields of a register.
+    fn fmt_fields(&self, f: &mut fmt::Formatter<'_>, register: Register) -> fmt::Result {
+        match register {
+            Register::STATUS => {
+                let status = self.value::<Status>();
+                write!(
+                    f,
+                    " nvm_busy={} activity={} fifo_overrun={} fifo_full={} data_ready={}",
+                    status.is_nvm_busy(),
+                    status.is_activity_detected(),
+                    status.is_fifo_overrun(),
+                    status.is_fifo_full(),
+                    status.is_data_ready()
+                )
+            }
+            Register::FIFO_ENTRIES => {
+                write!(f, " entries={}", self.value::<FifoEntries>().entries())
+            }
+            Register::TEMP2 => {
+                let raw = self.value::<Temperature>().raw();
+                let celsius = sans_io::temperature_celsius(raw);
+                write!(f, " raw={} celsius={:.1}", raw, celsius)
+            }
+            Register::XDATA3 => {
+                let mut buf = [0u8; 9];
+                buf.copy_from_slice(&self.status_and_data[0x08..=0x10]);
+                let sample = sans_io::decode_sample(&buf);
+                write!(f, " x={} y={} z={}", sample.x, sample.y, sample.z)
+            }
+            Register::OFFSET_X_H => write!(f, " offset={}", self.value::<OffsetX>().offset()),
+            Register::OFFSET_Y_H => write!(f, " offset={}", self.value::<OffsetY>().offset()),
+            Register::OFFSET_Z_H => write!(f, " offset={}", self.value::<OffsetZ>().offset()),
+            Register::ACT_EN => {
+                let act_en = self.value::<ActEn>();
+                write!(f, " x={} y={} z={}", act_en.x(), act_en.y(), act_en.z())
+            }
+            Register::ACT_THRESH_H => {
+                write!(f, " threshold={}", self.value::<ActThresh>().threshold())
+            }
+            Register::ACT_COUNT => write!(f, " count={}", self.value::<ActCount>().count()),
+            Register::FILTER => {
+                let filter = self.value::<Filter>();
+                write!(f, " odr={:?} hpf={:?}", filter.odr(), filter.hpf_corner())
+            }
+            Register::FIFO_SAMPLES => {
+                write!(f, " samples={}", self.value::<FifoSamples>().samples())
+            }
+            Register::SYNC => write!(f, " mode={:?}", self.value::<ExtSync>().mode()),
+            Register::RANGE => {
+                let range = self.value::<regs::Range>();
+                let bits = range.to_bits() & regs::Range::RANGE_BITS;
+                write!(
+                    f,
+                    " i2c_speed={:?} int_pol={:?} range={:?}",
+                    range.i2c_speed(),
+                    range.interrupt_polarity(),
+                    bits.checked_sub(1).map(|index| self.ranges[index as usize])
+                )
+            }
+            Register::POWER_CTL => {
+                let mode = self.value::<Mode>();
+                write!(
+                    f,
+                    " standby={} temp_off={} drdy_off={}",
+                    mode.is_in_standby(),
+                    mode.is_temperature_off(),
+                    mode.is_data_ready_off()
+                )
+            }
+            Register::SELF_TEST => {
+                let self_test = self.value::<SelfTest>();
+                write!(f, " st1={} st2={}", self_test.st1(), self_test.st2())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for RegisterDump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (register, value) in self.iter() {
+            write!(
+                f,
+                "0x{:02X} {:?} = 0x{:02X}",
+                register.addr(),
+                register,
+                value
+            )?;
+            self.fmt_fields(f, register)?;
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::ToString;
+
+    use super::*;
+    use crate::config::{HpfCorner, Odr};
+    use crate::variant::Adxl355Variant;
+
+    /// Dump of a device just out of reset, with a sample in the data registers.
+    fn reset_dump() -> RegisterDump {
+        let mut status_and_data = [0u8; 17];
+        status_and_data[..4].copy_from_slice(&[0xAD, 0x1D, 0xED, 0x01]);
+        status_and_data[0x04] = 0x01;
+        status_and_data[0x08..]
+            .copy_from_slice(&[0x00, 0x01, 0x00, 0xFF, 0xFF, 0xF0, 0x40, 0x00, 0x00]);
+        let mut control = [0u8; 18];
+        for (byte, (_, value)) in control.iter_mut().zip(RESET_VALUES) {
+            *byte = value;
+        }
+        RegisterDump::new::<Adxl355Variant>(
+            status_and_data,
+            control,
+            [0x11, 0x22, 0x33, 0x44, 0x55],
+        )
+    }
+
+    fn with(dump: &RegisterDump, register: Register, value: u8) -> RegisterDump {
+        let mut dump = *dump;
+        let addr = register.addr() as usize;
+        match register.addr() {
+            0x00..=0x10 => dump.status_and_data[addr] = value,
+            0x1E..=0x2F => dump.control[addr - 0x1E] = value,
+            _ => dump.shadow[addr - 0x50] = value,
+        }
+        dump
+    }
+
+    #[test]
+    fn get_skips_the_fifo_data() {
+        let dump = reset_dump();
+        assert_eq!(dump.get(Register::PARTID), Some(0xED));
+        assert_eq!(dump.get(Register::RANGE), Some(0x81));
+        assert_eq!(dump.get(Register::SHADOW_REG5), Some(0x55));
+        assert_eq!(dump.get(Register::FIFO_DATA), None);
+        assert!(dump
+            .iter()
+            .all(|(register, _)| register != Register::FIFO_DATA));
+        assert_eq!(dump.value::<FifoSamples>().samples(), 96);
+    }
+
+    #[test]
+    fn diff_ignores_the_volatile_registers() {
+        let reference = reset_dump();
+        let dump = with(&reference, Register::STATUS, 0x00);
+        let dump = with(&dump, Register::XDATA1, 0x30);
+        assert_eq!(dump.diff(&reference).count(), 0);
+
+        let dump = with(&dump, Register::FILTER, 0x25);
+        let dump = with(&dump, Register::SHADOW_REG1, 0x10);
+        let mut diffs = dump.diff(&reference);
+        assert_eq!(
+            diffs.next(),
+            Some(RegisterDiff {
+                register: Register::FILTER,
+                expected: 0x00,
+                actual: 0x25
+            })
+        );
+        assert_eq!(
+            diffs.next(),
+            Some(RegisterDiff {
+                register: Register::SHADOW_REG1,
+                expected: 0x11,
+                actual: 0x10
+            })
+        );
+        assert_eq!(diffs.next(), None);
+    }
+
+    #[test]
+    fn diff_reset_lists_the_changed_control_registers() {
+        let dump = reset_dump();
+        assert_eq!(dump.diff_reset().count(), 0);
+
+        let dump = with(&dump, Register::POWER_CTL, 0x00);
+        let dump = with(&dump, Register::STATUS, 0x02);
+        let mut diffs = dump.diff_reset();
+        assert_eq!(
+            diffs.next(),
+            Some(RegisterDiff {
+                register: Register::POWER_CTL,
+                expected: 0x01,
+                actual: 0x00
+            })
+        );
+        assert_eq!(diffs.next(), None);
+    }
+
+    #[test]
+    fn diff_config_compares_the_configured_bits() {
+        let config = Config::new(Range::_4G, Odr::_125Hz, HpfCorner::_6_2084);
+        let dump = reset_dump();
+        let mut diffs = dump.diff_config(&config);
+        assert_eq!(
+            diffs.next(),
+            Some(RegisterDiff {
+                register: Register::FILTER,
+                expected: 0x25,
+                actual: 0x00
+            })
+        );
+        assert_eq!(
+            diffs.next(),
+            Some(RegisterDiff {
+                register: Register::RANGE,
+                expected: 0x82,
+                actual: 0x81
+            })
+        );
+        assert_eq!(diffs.next(), None);
+
+        let dump = with(&dump, Register::FILTER, 0x25);
+        let dump = with(&dump, Register::RANGE, 0x42);
+        assert_eq!(dump.diff_config(&config).count(), 0);
+    }
+
+    #[test]
+    fn register_diff_display() {
+        let diff = RegisterDiff {
+            register: Register::RANGE,
+            expected: 0x82,
+            actual: 0x81,
+        };
+        assert_eq!(diff.to_string(), "RANGE: expected 0x82, got 0x81");
+    }
+}
@@ -0,0 +1,45 @@
+// Copyright © 2023 Analog Devices, Inc. All Rights Reserved. This software is
+// proprietary to Analog Devices, Inc. and its licensors.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// How to empty the FIFO.
+pub enum FlushMethod {
+    /// Read and drop every entry, the device keeps measuring and the filters stay settled.
+    #[default]
+    Drain,
+    /// Enter standby mode and restore the previous mode, which resets the FIFO pointers.
+    /// The configuration is preserved, but the filters have to settle again.
+    StandbyToggle,
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Outcome of a FIFO read reporting data loss.
+pub struct FifoReadOutcome {
+    /// Number of samples retrieved.
+    pub samples: usize,
+    /// The FIFO overran before the read, the oldest samples were lost.
+    pub overrun: bool,
+    /// Number of entries dropped because they did not belong to a complete sample.
+    pub discarded_entries: u8,
+}
+
+impl FifoReadOutcome {
+    /// No data was lost before or during the read.
+    pub fn is_lossless(&self) -> bool {
+        !self.overrun && self.discarded_entries == 0
+    }
+}
@@ -0,0 +1,477 @@
+// Copyright © 2023 Analog Devices, Inc. All Rights Reserved. This software is
+// proprietary to Analog Devices, Inc. and its licensors.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use micromath::vector::I32x3;
+
+use crate::dump::{RegisterDiff, RegisterDump, RESET_VALUES};
+use crate::identity::DeviceInfo;
+use crate::modes::Mode;
+use crate::register::{Register, RegisterBlock};
+
+/// Number of control registers tracked by a [`ControlImage`].
+const CONTROL_LEN: usize = 17;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Values the driver last wrote to the control registers, [`Register::OFFSET_X_H`] to
+/// [`Register::SELF_TEST`]. The registers not written since the driver was created hold an
+/// unknown value, all of them are known after a reset.
+pub struct ControlImage {
+    values: [Option<u8>; CONTROL_LEN],
+}
+
+impl ControlImage {
+    /// Image with no known register.
+    pub const fn unknown() -> Self {
+        ControlImage {
+            values: [None; CONTROL_LEN],
+        }
+    }
+
+    /// Image of the control registers after a reset.
+    pub fn after_reset() -> Self {
+        let mut image = Self::unknown();
+        for (register, value) in RESET_VALUES {
+            image.record(register, &[value]);
+        }
+        image
+    }
+
+    /// Index of a tracked register.
+    fn index(register: Register) -> Option<usize> {
+        let start = RegisterBlock::CONTROL.start().addr();
+        let index = register.addr().checked_sub(start)? as usize;
+        (index < CONTROL_LEN).then_some(index)
+    }
+
+    /// Get the value last written to a register, `None` if unknown or not tracked.
+    pub fn get(&self, register: Register) -> Option<u8> {
+        self.values[Self::index(register)?]
+    }
+
+    /// Record a write of `data` from `start_register`, the registers not tracked are ignored.
+    pub fn record(&mut self, start_register: Register, data: &[u8]) {
+        let first = start_register.addr() as usize;
+        let control_start = RegisterBlock::CONTROL.start().addr() as usize;
+        for (offset, &value) in data.iter().enumerate() {
+            let index = (first + offset).checked_sub(control_start);
+            if let Some(index) = index.filter(|&index| index < CONTROL_LEN) {
+                self.values[index] = Some(value);
+            }
+        }
+    }
+
+    /// Iterate over the known registers and their values, in address order.
+    pub fn iter(&self) -> impl Iterator<Item = (Register, u8)> + '_ {
+        let start = RegisterBlock::CONTROL.start().addr();
+        self.values
+            .iter()
+            .zip(start..)
+            .filter_map(|(value, addr)| Some((Register::try_from(addr).ok()?, (*value)?)))
+    }
+
+    /// List the known registers holding a different value in `dump`.
+    pub fn diff<'a>(&'a self, dump: &'a RegisterDump) -> impl Iterator<Item = RegisterDiff> + 'a {
+        self.diff_with(move |register| dump.get(register))
+    }
+
+    /// List the known registers holding a different value than returned by `get`.
+    fn diff_with<'a>(
+        &'a self,
+        get: impl Fn(Register) -> Option<u8> + 'a,
+    ) -> impl Iterator<Item = RegisterDiff> + 'a {
+        self.iter().filter_map(move |(register, expected)| {
+            let actual = get(register)?;
+            (actual != expected).then_some(RegisterDiff {
+                register,
+                expected,
+                actual,
+            })
+        })
+    }
+}
+
+impl Default for ControlImage {
+    fn default() -> Self {
+        Self::unknown()
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Registers compared by a health check, read at [`RegisterBlock::IDENTITY`],
+/// [`RegisterBlock::CONTROL`] and [`RegisterBlock::SHADOW`]. STATUS and the data registers are
+/// left out, reading them would clear the data ready flag.
+pub struct HealthRegisters {
+    /// DEVID_AD, DEVID_MST, PARTID and REVID.
+    pub identity: [u8; 4],
+    /// [`Register::OFFSET_X_H`] to [`Register::RESET`].
+    pub control: [u8; 18],
+    /// [`Register::SHADOW_REG1`] to [`Register::SHADOW_REG5`].
+    pub shadow: [u8; 5],
+}
+
+impl HealthRegisters {
+    /// Get the value of a control register, `None` for the other registers.
+    fn control(&self, register: Register) -> Option<u8> {
+        let start = RegisterBlock::CONTROL.start().addr();
+        let index = register.addr().checked_sub(start)? as usize;
+        self.control.get(index).copied()
+    }
+}
+
+impl From<&RegisterDump> for HealthRegisters {
+    fn from(dump: &RegisterDump) -> Self {
+        HealthRegisters {
+            identity: dump_block(dump, RegisterBlock::IDENTITY),
+            control: dump_block(dump, RegisterBlock::CONTROL),
+            shadow: dump_block(dump, RegisterBlock::SHADOW),
+        }
+    }
+}
+
+/// Get the values of a register block from a dump.
+fn dump_block<const N: usize>(dump: &RegisterDump, block: RegisterBlock<N>) -> [u8; N] {
+    let mut buf = [0u8; N];
+    for (byte, addr) in buf.iter_mut().zip(block.start().addr()..) {
+        let register = Register::try_from(addr).ok();
+        *byte = register.and_then(|r| dump.get(r)).unwrap_or_default();
+    }
+    buf
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Counter of consecutive identical acceleration samples.
+pub struct StuckSampleDetector {
+    last: Option<[i32; 3]>,
+    identical: u16,
+}
+
+impl StuckSampleDetector {
+    /// Record a sample read from the device.
+    pub fn update(&mut self, sample: I32x3) {
+        let sample = [sample.x, sample.y, sample.z];
+        if self.last == Some(sample) {
+            self.identical = self.identical.saturating_add(1);
+        } else {
+            self.last = Some(sample);
+            self.identical = 1;
+        }
+    }
+
+    /// Number of identical samples read in a row, including the first one of the run: 1 after
+    /// a sample differing from the previous one, 0 before any sample.
+    pub fn identical(&self) -> u16 {
+        self.identical
+    }
+
+    /// Forget the samples read so far.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Options of the driver health check.
+pub struct HealthPolicy {
+    /// Number of identical samples in a row reported as a stuck sample, 0 disables the check.
+    pub stuck_sample_threshold: u16,
+    /// Write the [`ControlImage`] back when the control registers drifted. Nothing is written
+    /// when the identity or the shadow registers changed, the device may not be the one the
+    /// image was written to.
+    pub reapply: bool,
+}
+
+impl Default for HealthPolicy {
+    /// By default, 16 identical samples in a row are reported as stuck and nothing is
+    /// written back.
+    fn default() -> Self {
+        HealthPolicy {
+            stuck_sample_threshold: 16,
+            reapply: false,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Outcome of a driver health check.
+pub struct HealthReport {
+    /// DEVID_AD, DEVID_MST, PARTID or REVID differ from the values read at construction.
+    pub identity_changed: bool,
+    /// The shadow registers differ from the values read at construction.
+    pub shadow_mismatch: bool,
+    /// The device is in standby while the driver last put it in measure mode.
+    pub unexpected_standby: bool,
+    /// The control registers hold their reset values while the driver wrote other values,
+    /// typically after a brown-out.
+    pub reset_detected: bool,
+    /// Number of identical samples read in a row.
+    pub identical_samples: u16,
+    /// `identical_samples` reached the [`HealthPolicy::stuck_sample_threshold`].
+    pub stuck_sample: bool,
+    /// The [`ControlImage`] was written back to the device, see [`HealthReport::should_reapply`].
+    pub reapplied: bool,
+    drift: [Option<RegisterDiff>; CONTROL_LEN],
+}
+
+impl HealthReport {
+    /// Evaluate the health of the device from its registers.
+    pub fn evaluate(
+        registers: &HealthRegisters,
+        image: &ControlImage,
+        info: &DeviceInfo,
+        shadow_values: [u8; 5],
+        identical_samples: u16,
+        policy: &HealthPolicy,
+    ) -> Self {
+        let mut drift = [None; CONTROL_LEN];
+        for (slot, diff) in drift
+            .iter_mut()
+            .zip(image.diff_with(|register| registers.control(register)))
+        {
+            *slot = Some(diff);
+        }
+
+        let expected_mode = image.get(Register::POWER_CTL).map(Mode);
+        let mode = Mode(registers.control(Register::POWER_CTL).unwrap_or_default());
+        let drifted = drift.iter().any(Option::is_some);
+        let reset = RESET_VALUES
+            .iter()
+            .all(|&(register, value)| registers.control(register) == Some(value));
+
+        HealthReport {
+            identity_changed: DeviceInfo::from_registers(registers.identity) != *info,
+            shadow_mismatch: registers.shadow != shadow_values,
+            unexpected_standby: mode.is_in_standby()
+                && expected_mode.map_or(false, |expected| !expected.is_in_standby()),
+            reset_detected: drifted && reset,
+            identical_samples,
+            stuck_sample: policy.stuck_sample_threshold != 0
+                && identical_samples >= policy.stuck_sample_threshold,
+            reapplied: false,
+            drift,
+        }
+    }
+
+    /// Control registers which do not hold the value last written by the driver.
+    pub fn drift(&self) -> impl Iterator<Item = &RegisterDiff> {
+        self.drift.iter().flatten()
+    }
+
+    /// Check if the control registers drifted from the values last written by the driver.
+    pub fn has_drifted(&self) -> bool {
+        self.drift().next().is_some()
+    }
+
+    /// Check if the [`ControlImage`] should be written back under `policy`: the control
+    /// registers drifted or the device left measurement mode, and the identity and shadow
+    /// registers still match the device the image was written to.
+    pub fn should_reapply(&self, policy: &HealthPolicy) -> bool {
+        policy.reapply
+            && !self.identity_changed
+            && !self.shadow_mismatch
+            && (self.has_drifted() || self.unexpected_standby)
+    }
+
+    /// Check if no issue was found.
+    pub fn is_healthy(&self) -> bool {
+        !self.identity_changed
+            && !self.shadow_mismatch
+            && !self.unexpected_standby
+            && !self.stuck_sample
+            && !self.has_drifted()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDENTITY: [u8; 4] = [0xAD, 0x1D, 0xED, 0x01];
+    const SHADOW: [u8; 5] = [0x11, 0x22, 0x33, 0x44, 0x55];
+
+    /// Registers of a device configured by the driver and in measurement mode.
+    fn registers(image: &ControlImage) -> HealthRegisters {
+        let mut control = [0u8; 18];
+        for (byte, (register, value)) in control.iter_mut().zip(RESET_VALUES) {
+            *byte = image.get(register).unwrap_or(value);
+        }
+        HealthRegisters {
+            identity: IDENTITY,
+            control,
+            shadow: SHADOW,
+        }
+    }
+
+    fn registers_after_reset() -> HealthRegisters {
+        registers(&ControlImage::after_reset())
+    }
+
+    fn configured() -> ControlImage {
+        let mut image = ControlImage::after_reset();
+        image.record(Register::FILTER, &[0x25]);
+        image.record(Register::POWER_CTL, &[0x00]);
+        image
+    }
+
+    fn evaluate(registers: &HealthRegisters, image: &ControlImage, identical: u16) -> HealthReport {
+        let info = DeviceInfo::from_registers(IDENTITY);
+        let policy = HealthPolicy::default();
+        HealthReport::evaluate(registers, image, &info, SHADOW, identical, &policy)
+    }
+
+    #[test]
+    fn control_image_records_the_tracked_registers() {
+        let mut image = ControlImage::unknown();
+        assert_eq!(image.iter().count(), 0);
+        // DEVID_AD to OFFSET_X_H are not tracked, RESET is not either.
+        image.record(Register::TEMP2, &[0xAA, 0xBB, 0xCC]);
+        image.record(Register::POWER_CTL, &[0x06, 0x02, 0x52]);
+        let mut known = image.iter();
+        assert_eq!(known.next(), Some((Register::POWER_CTL, 0x06)));
+        assert_eq!(known.next(), Some((Register::SELF_TEST, 0x02)));
+        assert_eq!(known.next(), None);
+        assert_eq!(image.get(Register::RESET), None);
+        assert_eq!(ControlImage::after_reset().iter().count(), CONTROL_LEN);
+    }
+
+    #[test]
+    fn healthy_device() {
+        let image = configured();
+        let report = evaluate(&registers(&image), &image, 1);
+        assert!(report.is_healthy());
+        assert!(!report.reset_detected);
+        assert!(!report.should_reapply(&HealthPolicy {
+            reapply: true,
+            ..Default::default()
+        }));
+    }
+
+    #[test]
+    fn reports_drift_and_silent_reset() {
+        let image = configured();
+        let mut registers = registers(&image);
+        registers.control[0x28 - 0x1E] = 0x00;
+        let report = evaluate(&registers, &image, 1);
+        assert!(report.has_drifted());
+        assert!(!report.reset_detected);
+        assert!(!report.unexpected_standby);
+        let mut drift = report.drift();
+        assert_eq!(
+            drift.next(),
+            Some(&RegisterDiff {
+                register: Register::FILTER,
+                expected: 0x25,
+                actual: 0x00
+            })
+        );
+        assert_eq!(drift.next(), None);
+
+        let registers = registers_after_reset();
+        let report = evaluate(&registers, &image, 1);
+        assert!(report.reset_detected);
+        assert!(report.unexpected_standby);
+        assert_eq!(report.drift().count(), 2);
+        assert!(report.should_reapply(&HealthPolicy {
+            reapply: true,
+            ..Default::default()
+        }));
+        assert!(!report.should_reapply(&HealthPolicy::default()));
+    }
+
+    #[test]
+    fn does_not_reapply_to_another_device() {
+        let image = configured();
+        let policy = HealthPolicy {
+            reapply: true,
+            ..Default::default()
+        };
+
+        let mut registers = registers_after_reset();
+        registers.identity[3] = 0x02;
+        let report = evaluate(&registers, &image, 1);
+        assert!(report.identity_changed);
+        assert!(report.has_drifted());
+        assert!(!report.should_reapply(&policy));
+
+        let mut registers = registers_after_reset();
+        registers.shadow[0] = 0x10;
+        let report = evaluate(&registers, &image, 1);
+        assert!(report.shadow_mismatch);
+        assert!(!report.is_healthy());
+        assert!(!report.should_reapply(&policy));
+    }
+
+    #[test]
+    fn reports_stuck_samples() {
+        let image = configured();
+        let registers = registers(&image);
+        let report = evaluate(&registers, &image, 15);
+        assert!(!report.stuck_sample);
+        let report = evaluate(&registers, &image, 16);
+        assert!(report.stuck_sample);
+        assert!(!report.is_healthy());
+
+        let info = DeviceInfo::from_registers(IDENTITY);
+        let policy = HealthPolicy {
+            stuck_sample_threshold: 0,
+            reapply: false,
+        };
+        let report = HealthReport::evaluate(&registers, &image, &info, SHADOW, 1000, &policy);
+        assert!(!report.stuck_sample);
+    }
+
+    #[test]
+    fn unknown_mode_is_not_an_unexpected_standby() {
+        let mut image = ControlImage::unknown();
+        image.record(Register::FILTER, &[0x25]);
+        let mut registers = registers_after_reset();
+        registers.control[0x28 - 0x1E] = 0x25;
+        let report = evaluate(&registers, &image, 0);
+        assert!(!report.unexpected_standby);
+        assert!(report.is_healthy());
+    }
+
+    #[test]
+    fn registers_from_a_dump() {
+        let mut status_and_data = [0u8; 17];
+        status_and_data[..4].copy_from_slice(&IDENTITY);
+        let mut control = [0u8; 18];
+        control[0x2D - 0x1E] = 0x06;
+        let dump =
+            RegisterDump::new::<crate::variant::Adxl355Variant>(status_and_data, control, SHADOW);
+        let registers = HealthRegisters::from(&dump);
+        assert_eq!(registers.identity, IDENTITY);
+        assert_eq!(registers.control, control);
+        assert_eq!(registers.shadow, SHADOW);
+    }
+
+    #[test]
+    fn stuck_sample_detector_counts_identical_samples() {
+        let mut detector = StuckSampleDetector::default();
+        assert_eq!(detector.identical(), 0);
+        detector.update(I32x3 { x: 1, y: 2, z: 3 });
+        assert_eq!(detector.identical(), 1);
+        detector.update(I32x3 { x: 1, y: 2, z: 3 });
+        detector.update(I32x3 { x: 1, y: 2, z: 3 });
+        assert_eq!(detector.identical(), 3);
+        detector.update(I32x3 { x: 1, y: 2, z: 4 });
+        assert_eq!(detector.identical(), 1);
+        detector.clear();
+        assert_eq!(detector.identical(), 0);
+    }
+}
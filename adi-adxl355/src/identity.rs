@@ -0,0 +1,106 @@
+// Copyright © 2023 Analog Devices, Inc. All Rights Reserved. This software is
+// proprietary to Analog Devices, Inc. and its licensors.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::variant::Variant;
+
+/// Analog Devices ID, expected in the [`crate::register::Register::DEVID_AD`] register.
+pub const DEVID_AD: u8 = 0xAD;
+
+/// Analog Devices MEMS ID, expected in the [`crate::register::Register::DEVID_MST`] register.
+pub const DEVID_MST: u8 = 0x1D;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Identification registers of the device, read in one burst from
+/// [`crate::register::Register::DEVID_AD`] to [`crate::register::Register::REVID`].
+pub struct DeviceInfo {
+    /// Analog Devices ID, 0xAD.
+    pub devid_ad: u8,
+    /// Analog Devices MEMS ID, 0x1D.
+    pub devid_mst: u8,
+    /// Device ID, see [`Variant::PART_ID`].
+    pub part_id: u8,
+    /// Product revision ID, it has no fixed value and is not validated.
+    pub revision_id: u8,
+}
+
+impl DeviceInfo {
+    /// Create the device info from the DEVID_AD, DEVID_MST, PARTID and REVID register values.
+    pub fn from_registers(buf: [u8; 4]) -> Self {
+        DeviceInfo {
+            devid_ad: buf[0],
+            devid_mst: buf[1],
+            part_id: buf[2],
+            revision_id: buf[3],
+        }
+    }
+
+    /// Check the identification registers against the values expected for the [`Variant`].
+    pub fn check<VARIANT: Variant>(&self) -> Result<(), IdentityMismatch> {
+        let mismatch = IdentityMismatch {
+            devid_ad: (self.devid_ad != DEVID_AD).then_some(self.devid_ad),
+            devid_mst: (self.devid_mst != DEVID_MST).then_some(self.devid_mst),
+            part_id: (self.part_id != VARIANT::PART_ID).then_some(self.part_id),
+            expected_part_id: VARIANT::PART_ID,
+        };
+
+        if mismatch.devid_ad.is_none() && mismatch.devid_mst.is_none() && mismatch.part_id.is_none()
+        {
+            Ok(())
+        } else {
+            Err(mismatch)
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Identification registers which did not hold the expected value.
+/// Each field contains the value read when it does not match, `None` otherwise.
+pub struct IdentityMismatch {
+    /// Value read from DEVID_AD if it is not [`DEVID_AD`].
+    pub devid_ad: Option<u8>,
+    /// Value read from DEVID_MST if it is not [`DEVID_MST`].
+    pub devid_mst: Option<u8>,
+    /// Value read from PARTID if it is not `expected_part_id`.
+    pub part_id: Option<u8>,
+    /// Part ID expected for the device variant.
+    pub expected_part_id: u8,
+}
+
+impl core::fmt::Display for IdentityMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut separator = "";
+        if let Some(devid_ad) = self.devid_ad {
+            write!(f, "DEVID_AD 0x{devid_ad:02X} (expected 0x{DEVID_AD:02X})")?;
+            separator = ", ";
+        }
+        if let Some(devid_mst) = self.devid_mst {
+            write!(
+                f,
+                "{separator}DEVID_MST 0x{devid_mst:02X} (expected 0x{DEVID_MST:02X})"
+            )?;
+            separator = ", ";
+        }
+        if let Some(part_id) = self.part_id {
+            write!(
+                f,
+                "{separator}PARTID 0x{part_id:02X} (expected 0x{:02X})",
+                self.expected_part_id
+            )?;
+        }
+        Ok(())
+    }
+}
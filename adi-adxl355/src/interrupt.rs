@@ -14,6 +14,7 @@
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// ADXL355 interrupt pins configuration.
 pub struct InterruptConfig(pub u8);
 
@@ -14,6 +14,7 @@
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// ADXL355 mode of operation.
 pub struct Mode(pub u8);
 
@@ -72,6 +73,41 @@ impl Default for Mode {
     }
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Summary of the power control settings of the ADXL355.
+pub struct PowerState {
+    /// The temperature and acceleration datapaths are operating.
+    pub measuring: bool,
+    /// Temperature processing is enabled.
+    pub temperature_enabled: bool,
+    /// The DRDY output signals data ready instead of being forced to 0.
+    pub drdy_output_enabled: bool,
+}
+
+impl From<Mode> for PowerState {
+    fn from(mode: Mode) -> Self {
+        PowerState {
+            measuring: !mode.is_in_standby(),
+            temperature_enabled: !mode.is_temperature_off(),
+            drdy_output_enabled: !mode.is_data_ready_off(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Outcome of an operation run while the device was temporarily put in standby mode.
+pub struct StandbyReport<T> {
+    /// Value returned by the operation.
+    pub output: T,
+    /// [`Mode`] the device was in before entering standby, and which has been restored.
+    pub previous_mode: Mode,
+    /// Number of complete samples held in the FIFO that were discarded when entering standby.
+    pub lost_samples: u8,
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
@@ -83,11 +119,45 @@ pub enum I2cSpeedMode {
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 /// ADXL355 external synchronization and interpolation options.
 pub enum ExternalSyncMode {
     NoExtSync = 0,
+    ExtSyncNoInterpolation = 0x01,
     ExtSyncWithInterpolation = 0x02,
+    /// Internal synchronization, with the device running on the external clock.
+    ExtClockNoExtSync = 0x04,
     ExtSyncExtClockNoInterpolation = 0x05,
     ExtSyncExtClockWithInterpolation = 0x06,
 }
+
+impl ExternalSyncMode {
+    /// The device runs on the external clock on pin INT2 instead of its internal clock.
+    pub fn uses_external_clock(self) -> bool {
+        self as u8 & 0x04 != 0
+    }
+
+    /// The samples are synchronized on the pulses on pin DRDY.
+    pub fn uses_external_sync(self) -> bool {
+        self as u8 & 0x03 != 0
+    }
+}
+
+impl TryFrom<u8> for ExternalSyncMode {
+    type Error = u8;
+
+    /// Get the mode from the EXT_CLK and EXT_SYNC bits of the SYNC register value,
+    /// the reserved EXT_SYNC setting `0b11` is returned as error.
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value & 0x07 {
+            0x00 => Ok(ExternalSyncMode::NoExtSync),
+            0x01 => Ok(ExternalSyncMode::ExtSyncNoInterpolation),
+            0x02 => Ok(ExternalSyncMode::ExtSyncWithInterpolation),
+            0x04 => Ok(ExternalSyncMode::ExtClockNoExtSync),
+            0x05 => Ok(ExternalSyncMode::ExtSyncExtClockNoInterpolation),
+            0x06 => Ok(ExternalSyncMode::ExtSyncExtClockWithInterpolation),
+            reserved => Err(reserved),
+        }
+    }
+}
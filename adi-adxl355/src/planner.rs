@@ -0,0 +1,207 @@
+// Copyright © 2023 Analog Devices, Inc. All Rights Reserved. This software is
+// proprietary to Analog Devices, Inc. and its licensors.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Filter planning from a requested passband.
+//!
+//! The low-pass corner is a quarter of the ODR and the high-pass corner a coefficient of the
+//! ODR, [`FilterResponse::plan`] picks the [`Odr`] and [`HpfCorner`] covering a passband in
+//! Hertz and reports the resulting corners, noise and settling time.
+
+use micromath::F32Ext;
+
+use crate::config::{Config, HpfCorner, Odr, Range};
+use crate::sync::ExternalSync;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Band of frequencies to measure, in Hertz.
+pub struct Passband {
+    /// Lowest frequency to pass, 0 to keep the DC component and disable the high-pass filter.
+    pub low_hz: f32,
+    /// Highest frequency to pass.
+    pub high_hz: f32,
+}
+
+impl Passband {
+    /// Creates a passband from `low_hz` to `high_hz`.
+    pub fn new(low_hz: f32, high_hz: f32) -> Self {
+        Passband { low_hz, high_hz }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
+/// No configuration covers the requested [`Passband`].
+pub enum PlanError {
+    /// The low frequency is negative or not below the high frequency.
+    #[cfg_attr(feature = "std", error("invalid passband"))]
+    InvalidPassband,
+    /// No ODR up to the maximum data rate has a low-pass corner at or above the high frequency.
+    #[cfg_attr(
+        feature = "std",
+        error("passband above the low-pass corner of the allowed ODRs")
+    )]
+    HighFrequencyOutOfReach,
+}
+
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Figures of a [`Config`] at the rates the device runs at with an [`ExternalSync`].
+pub struct FilterResponse {
+    /// Configuration the figures are computed for.
+    pub config: Config,
+    /// Rate in Hertz at which samples are output.
+    pub odr_hz: f32,
+    /// Low-pass filter −3 dB corner in Hertz.
+    pub lpf_hz: f32,
+    /// High-pass filter −3 dB corner in Hertz, `None` when the filter is off.
+    pub hpf_hz: Option<f32>,
+    /// Typical noise density in µg/√Hz.
+    pub noise_density_ug: f32,
+    /// Time in microseconds for the filters to settle after entering measurement mode.
+    pub settling_time_us: u64,
+}
+
+impl FilterResponse {
+    /// Compute the figures of `config` with `sync`.
+    pub fn new(config: Config, sync: &ExternalSync) -> Self {
+        FilterResponse {
+            config,
+            odr_hz: sync.effective_odr(config.odr),
+            lpf_hz: sync.effective_lpf(config.odr),
+            hpf_hz: sync.effective_hpf(config.odr, config.hpf),
+            noise_density_ug: config.range.noise_density_ug(),
+            settling_time_us: sync.scale_time_us(config.settling_time_us()),
+        }
+    }
+
+    /// Choose the configuration covering `passband` at `range`, with an ODR up to
+    /// `max_odr_hz`, on the internal clock.
+    ///
+    /// The slowest ODR whose low-pass corner is at or above the high frequency is chosen, which
+    /// keeps the noise bandwidth and the data rate lowest. The high-pass corner is the highest
+    /// one at or below the low frequency, the filter is off when the low frequency is 0 or
+    /// below every corner.
+    pub fn plan(passband: Passband, range: Range, max_odr_hz: f32) -> Result<Self, PlanError> {
+        // Written to reject NaN as well.
+        if !(passband.low_hz >= 0.0 && passband.low_hz < passband.high_hz) {
+            return Err(PlanError::InvalidPassband);
+        }
+
+        let odr = Odr::ALL
+            .into_iter()
+            .rev()
+            .find(|odr| odr.odr() <= max_odr_hz && odr.lpf() >= passband.high_hz)
+            .ok_or(PlanError::HighFrequencyOutOfReach)?;
+        // The corners are ordered from the highest to the lowest.
+        let hpf = HpfCorner::ALL
+            .into_iter()
+            .skip(1)
+            .find(|hpf| {
+                hpf.corner_hz(odr)
+                    .map_or(false, |corner_hz| corner_hz <= passband.low_hz)
+            })
+            .unwrap_or(HpfCorner::Off);
+
+        Ok(FilterResponse::new(
+            Config::new(range, odr, hpf),
+            &ExternalSync::default(),
+        ))
+    }
+
+    /// Expected RMS noise in µg over the band between the −3 dB corners.
+    pub fn noise_rms_ug(&self) -> f32 {
+        let bandwidth_hz = self.lpf_hz - self.hpf_hz.unwrap_or(0.0);
+        self.noise_density_ug * F32Ext::sqrt(bandwidth_hz)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plan(low_hz: f32, high_hz: f32, max_odr_hz: f32) -> Result<FilterResponse, PlanError> {
+        FilterResponse::plan(Passband::new(low_hz, high_hz), Range::_2G, max_odr_hz)
+    }
+
+    #[test]
+    fn plans_the_slowest_odr_covering_the_passband() {
+        let response = plan(0.5, 200.0, 4000.0).unwrap();
+        assert_eq!(response.config.range, Range::_2G);
+        assert_eq!(response.config.odr.val(), Odr::_1000Hz.val());
+        assert_eq!(response.config.hpf.val(), HpfCorner::_1_5545.val());
+        assert_eq!(response.odr_hz, 1000.0);
+        assert_eq!(response.lpf_hz, 250.0);
+        let hpf_hz = response.hpf_hz.unwrap();
+        assert!((hpf_hz - 0.155_45).abs() < 1e-5);
+        assert_eq!(
+            response.settling_time_us,
+            response.config.settling_time_us()
+        );
+
+        // A corner exactly at the high frequency is enough.
+        let response = plan(0.0, 250.0, 4000.0).unwrap();
+        assert_eq!(response.config.odr.val(), Odr::_1000Hz.val());
+    }
+
+    #[test]
+    fn keeps_the_dc_component() {
+        let response = plan(0.0, 1.0, 4000.0).unwrap();
+        assert_eq!(response.config.odr.val(), Odr::_7_813Hz.val());
+        assert_eq!(response.config.hpf.val(), HpfCorner::Off.val());
+        assert_eq!(response.hpf_hz, None);
+
+        // Below the lowest corner, the high-pass filter stays off.
+        let response = plan(1e-7, 0.5, 4000.0).unwrap();
+        assert_eq!(response.config.odr.val(), Odr::_3_906Hz.val());
+        assert_eq!(response.config.hpf.val(), HpfCorner::Off.val());
+    }
+
+    #[test]
+    fn rejects_unreachable_and_invalid_passbands() {
+        assert_eq!(
+            plan(0.0, 200.0, 500.0).err(),
+            Some(PlanError::HighFrequencyOutOfReach)
+        );
+        assert_eq!(
+            plan(0.0, 1001.0, 4000.0).err(),
+            Some(PlanError::HighFrequencyOutOfReach)
+        );
+        assert_eq!(
+            plan(10.0, 10.0, 4000.0).err(),
+            Some(PlanError::InvalidPassband)
+        );
+        assert_eq!(
+            plan(-1.0, 10.0, 4000.0).err(),
+            Some(PlanError::InvalidPassband)
+        );
+        assert_eq!(
+            plan(f32::NAN, 10.0, 4000.0).err(),
+            Some(PlanError::InvalidPassband)
+        );
+    }
+
+    #[test]
+    fn noise_over_the_passband() {
+        let response = plan(0.0, 200.0, 4000.0).unwrap();
+        let expected = response.noise_density_ug * F32Ext::sqrt(250.0);
+        assert!((response.noise_rms_ug() - expected).abs() < 1e-3);
+
+        let response = Config::new(Range::_2G, Odr::_4000Hz, HpfCorner::_24_7).describe();
+        let bandwidth_hz = 1000.0 - 24.7e-4 * 4000.0;
+        let expected = response.noise_density_ug * F32Ext::sqrt(bandwidth_hz);
+        assert!((response.noise_rms_ug() - expected).abs() < 1e-3);
+    }
+}
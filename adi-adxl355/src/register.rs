@@ -13,6 +13,7 @@
 // limitations under the License.
 
 #[allow(dead_code, non_camel_case_types, clippy::upper_case_acronyms)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 /// ADXL355 register map.
@@ -152,4 +153,141 @@ impl Register {
                 | Register::SHADOW_REG5
         )
     }
+
+    /// Check if the register value changes on its own while the device is measuring.
+    pub fn volatile(self) -> bool {
+        matches!(
+            self,
+            Register::STATUS
+                | Register::FIFO_ENTRIES
+                | Register::TEMP2
+                | Register::TEMP1
+                | Register::XDATA3
+                | Register::XDATA2
+                | Register::XDATA1
+                | Register::YDATA3
+                | Register::YDATA2
+                | Register::YDATA1
+                | Register::ZDATA3
+                | Register::ZDATA2
+                | Register::ZDATA1
+                | Register::FIFO_DATA
+        )
+    }
+}
+
+impl TryFrom<u8> for Register {
+    type Error = u8;
+
+    /// Get the register at address `addr`, the unknown address is returned as error.
+    fn try_from(addr: u8) -> Result<Self, Self::Error> {
+        match addr {
+            0x00 => Ok(Register::DEVID_AD),
+            0x01 => Ok(Register::DEVID_MST),
+            0x02 => Ok(Register::PARTID),
+            0x03 => Ok(Register::REVID),
+            0x04 => Ok(Register::STATUS),
+            0x05 => Ok(Register::FIFO_ENTRIES),
+            0x06 => Ok(Register::TEMP2),
+            0x07 => Ok(Register::TEMP1),
+            0x08 => Ok(Register::XDATA3),
+            0x09 => Ok(Register::XDATA2),
+            0x0A => Ok(Register::XDATA1),
+            0x0B => Ok(Register::YDATA3),
+            0x0C => Ok(Register::YDATA2),
+            0x0D => Ok(Register::YDATA1),
+            0x0E => Ok(Register::ZDATA3),
+            0x0F => Ok(Register::ZDATA2),
+            0x10 => Ok(Register::ZDATA1),
+            0x11 => Ok(Register::FIFO_DATA),
+            0x1E => Ok(Register::OFFSET_X_H),
+            0x1F => Ok(Register::OFFSET_X_L),
+            0x20 => Ok(Register::OFFSET_Y_H),
+            0x21 => Ok(Register::OFFSET_Y_L),
+            0x22 => Ok(Register::OFFSET_Z_H),
+            0x23 => Ok(Register::OFFSET_Z_L),
+            0x24 => Ok(Register::ACT_EN),
+            0x25 => Ok(Register::ACT_THRESH_H),
+            0x26 => Ok(Register::ACT_THRESH_L),
+            0x27 => Ok(Register::ACT_COUNT),
+            0x28 => Ok(Register::FILTER),
+            0x29 => Ok(Register::FIFO_SAMPLES),
+            0x2A => Ok(Register::INT_MAP),
+            0x2B => Ok(Register::SYNC),
+            0x2C => Ok(Register::RANGE),
+            0x2D => Ok(Register::POWER_CTL),
+            0x2E => Ok(Register::SELF_TEST),
+            0x2F => Ok(Register::RESET),
+            0x50 => Ok(Register::SHADOW_REG1),
+            0x51 => Ok(Register::SHADOW_REG2),
+            0x52 => Ok(Register::SHADOW_REG3),
+            0x53 => Ok(Register::SHADOW_REG4),
+            0x54 => Ok(Register::SHADOW_REG5),
+            _ => Err(addr),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Block of `N` consecutive registers accessed in a single burst, starting at
+/// [`RegisterBlock::start`].
+pub struct RegisterBlock<const N: usize> {
+    start: Register,
+}
+
+impl<const N: usize> RegisterBlock<N> {
+    /// Create a block of `N` registers starting at `start`.
+    pub const fn new(start: Register) -> Self {
+        RegisterBlock { start }
+    }
+
+    /// First register of the block.
+    pub const fn start(self) -> Register {
+        self.start
+    }
+
+    /// Number of registers in the block.
+    #[allow(clippy::len_without_is_empty)]
+    pub const fn len(self) -> usize {
+        N
+    }
+}
+
+impl RegisterBlock<2> {
+    /// [`Register::TEMP2`] and [`Register::TEMP1`].
+    pub const TEMPERATURE: Self = RegisterBlock::new(Register::TEMP2);
+    /// [`Register::ACT_THRESH_H`] and [`Register::ACT_THRESH_L`].
+    pub const ACTIVITY_THRESHOLD: Self = RegisterBlock::new(Register::ACT_THRESH_H);
+}
+
+impl RegisterBlock<4> {
+    /// [`Register::DEVID_AD`] to [`Register::REVID`].
+    pub const IDENTITY: Self = RegisterBlock::new(Register::DEVID_AD);
+}
+
+impl RegisterBlock<5> {
+    /// [`Register::SHADOW_REG1`] to [`Register::SHADOW_REG5`].
+    pub const SHADOW: Self = RegisterBlock::new(Register::SHADOW_REG1);
+}
+
+impl RegisterBlock<6> {
+    /// [`Register::OFFSET_X_H`] to [`Register::OFFSET_Z_L`].
+    pub const OFFSETS: Self = RegisterBlock::new(Register::OFFSET_X_H);
+}
+
+impl RegisterBlock<9> {
+    /// [`Register::XDATA3`] to [`Register::ZDATA1`].
+    pub const ACCEL_DATA: Self = RegisterBlock::new(Register::XDATA3);
+}
+
+impl RegisterBlock<17> {
+    /// [`Register::DEVID_AD`] to [`Register::ZDATA1`], the identification, status and data
+    /// registers up to [`Register::FIFO_DATA`] excluded.
+    pub const STATUS_AND_DATA: Self = RegisterBlock::new(Register::DEVID_AD);
+}
+
+impl RegisterBlock<18> {
+    /// [`Register::OFFSET_X_H`] to [`Register::RESET`], the control registers.
+    pub const CONTROL: Self = RegisterBlock::new(Register::OFFSET_X_H);
 }
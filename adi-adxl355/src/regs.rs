@@ -0,0 +1,816 @@
+// Copyright © 2023 Analog Devices, Inc. All Rights Reserved. This software is
+// proprietary to Analog Devices, Inc. and its licensors.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Typed values of the ADXL355 registers.
+//!
+//! Each type wraps the value of one register, or of a high/low register pair, with getters and
+//! setters for its fields. The reserved bits read from the device are kept untouched by the
+//! setters, so a value read with `read_reg` and written back with `modify_reg` only changes the
+//! fields that were set. [`Status`], [`Mode`] and [`InterruptConfig`] are the typed values of
+//! the STATUS, POWER_CTL and INT_MAP registers.
+//!
+//! The acceleration data, FIFO data and shadow registers are read in bursts by the driver,
+//! see [`crate::register::RegisterBlock`].
+
+use crate::config::{self, HpfCorner, Odr};
+use crate::interrupt::{InterruptConfig, InterruptPolarity};
+use crate::modes::{ExternalSyncMode, I2cSpeedMode, Mode};
+use crate::register::Register;
+use crate::status::Status;
+use crate::variant::Variant;
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for u8 {}
+    impl Sealed for u16 {}
+}
+
+/// Bit representation of a register value: `u8` for a single register, `u16` for a high/low
+/// register pair.
+pub trait RegisterBits: sealed::Sealed + Copy {
+    /// Number of registers holding the value.
+    const LEN: usize;
+
+    /// Decode the value from the register bytes, high byte first.
+    fn from_bytes(bytes: &[u8]) -> Self;
+
+    /// Encode the value as register bytes, high byte first.
+    fn to_bytes(self, bytes: &mut [u8]);
+}
+
+impl RegisterBits for u8 {
+    const LEN: usize = 1;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        bytes[0]
+    }
+
+    fn to_bytes(self, bytes: &mut [u8]) {
+        bytes[0] = self;
+    }
+}
+
+impl RegisterBits for u16 {
+    const LEN: usize = 2;
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        u16::from_be_bytes([bytes[0], bytes[1]])
+    }
+
+    fn to_bytes(self, bytes: &mut [u8]) {
+        bytes[..2].copy_from_slice(&self.to_be_bytes());
+    }
+}
+
+/// Typed value of a register.
+pub trait RegisterValue: Copy {
+    /// Bit representation of the value.
+    type Bits: RegisterBits;
+
+    /// Register holding the value, the high byte for a register pair.
+    const REGISTER: Register;
+
+    /// Create the value from the register bits.
+    fn from_bits(bits: Self::Bits) -> Self;
+
+    /// Get the register bits.
+    fn to_bits(self) -> Self::Bits;
+}
+
+/// Typed value of a register that can be written.
+pub trait WritableRegister: RegisterValue {}
+
+/// Plain 8-bit value registers.
+macro_rules! value_register {
+    ($(#[$doc:meta])* $name:ident, $register:ident, $(#[$value_doc:meta])* $value:ident) => {
+        #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+        $(#[$doc])*
+        pub struct $name(u8);
+
+        impl $name {
+            $(#[$value_doc])*
+            pub fn $value(&self) -> u8 {
+                self.0
+            }
+        }
+
+        impl RegisterValue for $name {
+            type Bits = u8;
+            const REGISTER: Register = Register::$register;
+
+            fn from_bits(bits: u8) -> Self {
+                $name(bits)
+            }
+
+            fn to_bits(self) -> u8 {
+                self.0
+            }
+        }
+    };
+}
+
+value_register!(
+    /// DEVID_AD register, the Analog Devices ID.
+    DevIdAd,
+    DEVID_AD,
+    /// Analog Devices ID, 0xAD.
+    id
+);
+
+value_register!(
+    /// DEVID_MST register, the Analog Devices MEMS ID.
+    DevIdMst,
+    DEVID_MST,
+    /// Analog Devices MEMS ID, 0x1D.
+    id
+);
+
+value_register!(
+    /// PARTID register, the device ID.
+    PartId,
+    PARTID,
+    /// Device ID, see [`Variant::PART_ID`].
+    id
+);
+
+value_register!(
+    /// REVID register, the product revision ID.
+    RevId,
+    REVID,
+    /// Product revision ID.
+    id
+);
+
+impl RegisterValue for Status {
+    type Bits = u8;
+    const REGISTER: Register = Register::STATUS;
+
+    fn from_bits(bits: u8) -> Self {
+        Status(bits)
+    }
+
+    fn to_bits(self) -> u8 {
+        self.0
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// FIFO_ENTRIES register, the number of valid data samples in the FIFO.
+pub struct FifoEntries(u8);
+
+impl FifoEntries {
+    pub const ENTRIES_BITS: u8 = 0x7F;
+
+    /// Number of FIFO entries, from 0 to 96.
+    pub fn entries(&self) -> u8 {
+        self.0 & Self::ENTRIES_BITS
+    }
+}
+
+impl RegisterValue for FifoEntries {
+    type Bits = u8;
+    const REGISTER: Register = Register::FIFO_ENTRIES;
+
+    fn from_bits(bits: u8) -> Self {
+        FifoEntries(bits)
+    }
+
+    fn to_bits(self) -> u8 {
+        self.0
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// TEMP2 and TEMP1 registers, the uncalibrated temperature data.
+pub struct Temperature(u16);
+
+impl Temperature {
+    pub const TEMP_BITS: u16 = 0x0FFF;
+
+    /// Raw 12-bit temperature value.
+    pub fn raw(&self) -> u16 {
+        self.0 & Self::TEMP_BITS
+    }
+}
+
+impl RegisterValue for Temperature {
+    type Bits = u16;
+    const REGISTER: Register = Register::TEMP2;
+
+    fn from_bits(bits: u16) -> Self {
+        Temperature(bits)
+    }
+
+    fn to_bits(self) -> u16 {
+        self.0
+    }
+}
+
+/// Offset trim register pairs.
+macro_rules! offset_register {
+    ($(#[$doc:meta])* $name:ident, $register:ident) => {
+        #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+        $(#[$doc])*
+        pub struct $name(u16);
+
+        impl $name {
+            /// Offset trim, removed from the axis data bits\[19:4\].
+            pub fn offset(&self) -> i16 {
+                self.0 as i16
+            }
+
+            /// Set the offset trim.
+            pub fn set_offset(&mut self, offset: i16) -> &mut Self {
+                self.0 = offset as u16;
+                self
+            }
+        }
+
+        impl RegisterValue for $name {
+            type Bits = u16;
+            const REGISTER: Register = Register::$register;
+
+            fn from_bits(bits: u16) -> Self {
+                $name(bits)
+            }
+
+            fn to_bits(self) -> u16 {
+                self.0
+            }
+        }
+
+        impl WritableRegister for $name {}
+    };
+}
+
+offset_register!(
+    /// OFFSET_X_H and OFFSET_X_L registers, the x-axis offset trim.
+    OffsetX,
+    OFFSET_X_H
+);
+
+offset_register!(
+    /// OFFSET_Y_H and OFFSET_Y_L registers, the y-axis offset trim.
+    OffsetY,
+    OFFSET_Y_H
+);
+
+offset_register!(
+    /// OFFSET_Z_H and OFFSET_Z_L registers, the z-axis offset trim.
+    OffsetZ,
+    OFFSET_Z_H
+);
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// ACT_EN register, the axes taking part in activity detection.
+pub struct ActEn(u8);
+
+impl ActEn {
+    pub const ACT_X_BIT: u8 = 0x01;
+    pub const ACT_Y_BIT: u8 = 0x02;
+    pub const ACT_Z_BIT: u8 = 0x04;
+
+    /// Check if the x-axis takes part in activity detection.
+    pub fn x(&self) -> bool {
+        self.0 & Self::ACT_X_BIT == Self::ACT_X_BIT
+    }
+
+    /// Check if the y-axis takes part in activity detection.
+    pub fn y(&self) -> bool {
+        self.0 & Self::ACT_Y_BIT == Self::ACT_Y_BIT
+    }
+
+    /// Check if the z-axis takes part in activity detection.
+    pub fn z(&self) -> bool {
+        self.0 & Self::ACT_Z_BIT == Self::ACT_Z_BIT
+    }
+
+    /// Set whether the x-axis takes part in activity detection.
+    pub fn set_x(&mut self, enabled: bool) -> &mut Self {
+        self.0 = set_bits(self.0, Self::ACT_X_BIT, enabled);
+        self
+    }
+
+    /// Set whether the y-axis takes part in activity detection.
+    pub fn set_y(&mut self, enabled: bool) -> &mut Self {
+        self.0 = set_bits(self.0, Self::ACT_Y_BIT, enabled);
+        self
+    }
+
+    /// Set whether the z-axis takes part in activity detection.
+    pub fn set_z(&mut self, enabled: bool) -> &mut Self {
+        self.0 = set_bits(self.0, Self::ACT_Z_BIT, enabled);
+        self
+    }
+}
+
+impl RegisterValue for ActEn {
+    type Bits = u8;
+    const REGISTER: Register = Register::ACT_EN;
+
+    fn from_bits(bits: u8) -> Self {
+        ActEn(bits)
+    }
+
+    fn to_bits(self) -> u8 {
+        self.0
+    }
+}
+
+impl WritableRegister for ActEn {}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// ACT_THRESH_H and ACT_THRESH_L registers, the activity detection threshold.
+pub struct ActThresh(u16);
+
+impl ActThresh {
+    /// Threshold, with the significance of the bits\[18:3\] of the axis data.
+    pub fn threshold(&self) -> u16 {
+        self.0
+    }
+
+    /// Set the threshold.
+    pub fn set_threshold(&mut self, threshold: u16) -> &mut Self {
+        self.0 = threshold;
+        self
+    }
+}
+
+impl RegisterValue for ActThresh {
+    type Bits = u16;
+    const REGISTER: Register = Register::ACT_THRESH_H;
+
+    fn from_bits(bits: u16) -> Self {
+        ActThresh(bits)
+    }
+
+    fn to_bits(self) -> u16 {
+        self.0
+    }
+}
+
+impl WritableRegister for ActThresh {}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// ACT_COUNT register, the number of consecutive events above threshold to detect activity.
+pub struct ActCount(u8);
+
+impl ActCount {
+    /// Number of consecutive events above threshold.
+    pub fn count(&self) -> u8 {
+        self.0
+    }
+
+    /// Set the number of consecutive events above threshold.
+    pub fn set_count(&mut self, count: u8) -> &mut Self {
+        self.0 = count;
+        self
+    }
+}
+
+impl RegisterValue for ActCount {
+    type Bits = u8;
+    const REGISTER: Register = Register::ACT_COUNT;
+
+    fn from_bits(bits: u8) -> Self {
+        ActCount(bits)
+    }
+
+    fn to_bits(self) -> u8 {
+        self.0
+    }
+}
+
+impl WritableRegister for ActCount {}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// FILTER register, the high-pass and low-pass filters.
+pub struct Filter(u8);
+
+impl Filter {
+    pub const ODR_LPF_BITS: u8 = 0x0F;
+    pub const HPF_CORNER_BITS: u8 = 0x70;
+    pub const HPF_CORNER_SHIFT: u8 = 4;
+
+    /// Output data rate and low-pass filter corner, `None` for a reserved value.
+    pub fn odr(&self) -> Option<Odr> {
+        let bits = self.0 & Self::ODR_LPF_BITS;
+        (bits <= Odr::_3_906Hz.val()).then(|| Odr::from(bits))
+    }
+
+    /// High-pass filter corner, `None` for a reserved value.
+    pub fn hpf_corner(&self) -> Option<HpfCorner> {
+        let bits = self.0 & Self::HPF_CORNER_BITS;
+        (bits >> Self::HPF_CORNER_SHIFT <= HpfCorner::_0_0238.val()).then(|| HpfCorner::from(bits))
+    }
+
+    /// Set the output data rate and low-pass filter corner.
+    pub fn set_odr(&mut self, odr: Odr) -> &mut Self {
+        self.0 = (self.0 & !Self::ODR_LPF_BITS) | odr.val();
+        self
+    }
+
+    /// Set the high-pass filter corner.
+    pub fn set_hpf_corner(&mut self, hpf: HpfCorner) -> &mut Self {
+        self.0 = (self.0 & !Self::HPF_CORNER_BITS) | (hpf.val() << Self::HPF_CORNER_SHIFT);
+        self
+    }
+}
+
+impl RegisterValue for Filter {
+    type Bits = u8;
+    const REGISTER: Register = Register::FILTER;
+
+    fn from_bits(bits: u8) -> Self {
+        Filter(bits)
+    }
+
+    fn to_bits(self) -> u8 {
+        self.0
+    }
+}
+
+impl WritableRegister for Filter {}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// FIFO_SAMPLES register, the number of samples to store in the FIFO.
+pub struct FifoSamples(u8);
+
+impl FifoSamples {
+    pub const SAMPLES_BITS: u8 = 0x7F;
+
+    /// Number of samples to store in the FIFO, the watermark.
+    pub fn samples(&self) -> u8 {
+        self.0 & Self::SAMPLES_BITS
+    }
+
+    /// Set the number of samples to store in the FIFO, from 1 to 96.
+    pub fn set_samples(&mut self, samples: u8) -> &mut Self {
+        self.0 = (self.0 & !Self::SAMPLES_BITS) | (samples & Self::SAMPLES_BITS);
+        self
+    }
+}
+
+impl Default for FifoSamples {
+    /// The reset value, 0x60.
+    fn default() -> Self {
+        FifoSamples(0x60)
+    }
+}
+
+impl RegisterValue for FifoSamples {
+    type Bits = u8;
+    const REGISTER: Register = Register::FIFO_SAMPLES;
+
+    fn from_bits(bits: u8) -> Self {
+        FifoSamples(bits)
+    }
+
+    fn to_bits(self) -> u8 {
+        self.0
+    }
+}
+
+impl WritableRegister for FifoSamples {}
+
+impl RegisterValue for InterruptConfig {
+    type Bits = u8;
+    const REGISTER: Register = Register::INT_MAP;
+
+    fn from_bits(bits: u8) -> Self {
+        InterruptConfig(bits)
+    }
+
+    fn to_bits(self) -> u8 {
+        self.0
+    }
+}
+
+impl WritableRegister for InterruptConfig {}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// SYNC register, the external timing triggers.
+pub struct ExtSync(u8);
+
+impl ExtSync {
+    pub const EXT_SYNC_BITS: u8 = 0x03;
+    pub const EXT_CLK_BIT: u8 = 0x04;
+
+    /// Synchronization mode, `None` for a reserved combination.
+    pub fn mode(&self) -> Option<ExternalSyncMode> {
+        ExternalSyncMode::try_from(self.0 & (Self::EXT_CLK_BIT | Self::EXT_SYNC_BITS)).ok()
+    }
+
+    /// EXT_SYNC bits.
+    pub fn ext_sync(&self) -> u8 {
+        self.0 & Self::EXT_SYNC_BITS
+    }
+
+    /// Check if the external clock is enabled.
+    pub fn ext_clk(&self) -> bool {
+        self.0 & Self::EXT_CLK_BIT == Self::EXT_CLK_BIT
+    }
+
+    /// Set the synchronization mode.
+    pub fn set_mode(&mut self, mode: ExternalSyncMode) -> &mut Self {
+        self.0 = (self.0 & !(Self::EXT_CLK_BIT | Self::EXT_SYNC_BITS)) | mode as u8;
+        self
+    }
+}
+
+impl RegisterValue for ExtSync {
+    type Bits = u8;
+    const REGISTER: Register = Register::SYNC;
+
+    fn from_bits(bits: u8) -> Self {
+        ExtSync(bits)
+    }
+
+    fn to_bits(self) -> u8 {
+        self.0
+    }
+}
+
+impl WritableRegister for ExtSync {}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// RANGE register, the I2C speed, interrupt polarity and range.
+pub struct Range(u8);
+
+impl Range {
+    pub const I2C_HS_BIT: u8 = 0x80;
+    pub const INT_POL_BIT: u8 = 0x40;
+    pub const RANGE_BITS: u8 = 0x03;
+
+    /// I2C speed mode.
+    pub fn i2c_speed(&self) -> I2cSpeedMode {
+        if self.0 & Self::I2C_HS_BIT == Self::I2C_HS_BIT {
+            I2cSpeedMode::HighSpeed
+        } else {
+            I2cSpeedMode::Fast
+        }
+    }
+
+    /// Interrupt pins polarity.
+    pub fn interrupt_polarity(&self) -> InterruptPolarity {
+        if self.0 & Self::INT_POL_BIT == Self::INT_POL_BIT {
+            InterruptPolarity::ActiveHigh
+        } else {
+            InterruptPolarity::ActiveLow
+        }
+    }
+
+    /// Range of the given [`Variant`], `None` for the reserved value.
+    pub fn range<VARIANT: Variant>(&self) -> Option<config::Range> {
+        VARIANT::range_from_register(self.0)
+    }
+
+    /// Set the I2C speed mode.
+    pub fn set_i2c_speed(&mut self, mode: I2cSpeedMode) -> &mut Self {
+        self.0 = set_bits(self.0, Self::I2C_HS_BIT, mode == I2cSpeedMode::HighSpeed);
+        self
+    }
+
+    /// Set the interrupt pins polarity.
+    pub fn set_interrupt_polarity(&mut self, polarity: InterruptPolarity) -> &mut Self {
+        let active_high = polarity == InterruptPolarity::ActiveHigh;
+        self.0 = set_bits(self.0, Self::INT_POL_BIT, active_high);
+        self
+    }
+
+    /// Set the range.
+    pub fn set_range(&mut self, range: config::Range) -> &mut Self {
+        self.0 = (self.0 & !Self::RANGE_BITS) | range.val();
+        self
+    }
+}
+
+impl Default for Range {
+    /// The reset value, 0x81: high speed I2C, active low interrupts and the lowest range.
+    fn default() -> Self {
+        Range(0x81)
+    }
+}
+
+impl RegisterValue for Range {
+    type Bits = u8;
+    const REGISTER: Register = Register::RANGE;
+
+    fn from_bits(bits: u8) -> Self {
+        Range(bits)
+    }
+
+    fn to_bits(self) -> u8 {
+        self.0
+    }
+}
+
+impl WritableRegister for Range {}
+
+impl RegisterValue for Mode {
+    type Bits = u8;
+    const REGISTER: Register = Register::POWER_CTL;
+
+    fn from_bits(bits: u8) -> Self {
+        Mode(bits)
+    }
+
+    fn to_bits(self) -> u8 {
+        self.0
+    }
+}
+
+impl WritableRegister for Mode {}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// SELF_TEST register, the self test feature.
+pub struct SelfTest(u8);
+
+impl SelfTest {
+    pub const ST1_BIT: u8 = 0x01;
+    pub const ST2_BIT: u8 = 0x02;
+
+    /// Check if the self test mode is enabled.
+    pub fn st1(&self) -> bool {
+        self.0 & Self::ST1_BIT == Self::ST1_BIT
+    }
+
+    /// Check if the self test force is applied.
+    pub fn st2(&self) -> bool {
+        self.0 & Self::ST2_BIT == Self::ST2_BIT
+    }
+
+    /// Enable the self test mode.
+    pub fn set_st1(&mut self, enabled: bool) -> &mut Self {
+        self.0 = set_bits(self.0, Self::ST1_BIT, enabled);
+        self
+    }
+
+    /// Apply the self test force.
+    pub fn set_st2(&mut self, enabled: bool) -> &mut Self {
+        self.0 = set_bits(self.0, Self::ST2_BIT, enabled);
+        self
+    }
+}
+
+impl RegisterValue for SelfTest {
+    type Bits = u8;
+    const REGISTER: Register = Register::SELF_TEST;
+
+    fn from_bits(bits: u8) -> Self {
+        SelfTest(bits)
+    }
+
+    fn to_bits(self) -> u8 {
+        self.0
+    }
+}
+
+impl WritableRegister for SelfTest {}
+
+/// Set or clear the `mask` bits of `bits`.
+fn set_bits(bits: u8, mask: u8, set: bool) -> u8 {
+    if set {
+        bits | mask
+    } else {
+        bits & !mask
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::variant::{Adxl355Variant, Adxl357Variant};
+
+    #[test]
+    fn register_bits_are_high_byte_first() {
+        let mut bytes = [0; 2];
+        0x12_34u16.to_bytes(&mut bytes);
+        assert_eq!(bytes, [0x12, 0x34]);
+        assert_eq!(u16::from_bytes(&bytes), 0x12_34);
+        assert_eq!(u8::from_bytes(&[0xAB, 0xCD]), 0xAB);
+    }
+
+    #[test]
+    fn filter_setters_keep_the_reserved_bit() {
+        let mut filter = Filter::from_bits(0x80);
+        filter
+            .set_odr(Odr::_125Hz)
+            .set_hpf_corner(HpfCorner::_0_0954);
+        assert_eq!(filter.to_bits(), 0xD5);
+        assert_eq!(filter.odr().map(Odr::val), Some(Odr::_125Hz.val()));
+        assert_eq!(
+            filter.hpf_corner().map(HpfCorner::val),
+            Some(HpfCorner::_0_0954.val())
+        );
+
+        filter.set_odr(Odr::_4000Hz).set_hpf_corner(HpfCorner::Off);
+        assert_eq!(filter.to_bits(), 0x80);
+    }
+
+    #[test]
+    fn filter_reserved_values() {
+        let filter = Filter::from_bits(0x7B);
+        assert!(filter.odr().is_none());
+        assert!(filter.hpf_corner().is_none());
+    }
+
+    #[test]
+    fn fifo_samples_setter_keeps_the_reserved_bit() {
+        assert_eq!(FifoSamples::default().samples(), 96);
+        let mut samples = FifoSamples::from_bits(0x80);
+        samples.set_samples(0xFF);
+        assert_eq!(samples.to_bits(), 0xFF);
+        samples.set_samples(12);
+        assert_eq!(samples.to_bits(), 0x8C);
+        assert_eq!(samples.samples(), 12);
+    }
+
+    #[test]
+    fn ext_sync_setter_keeps_the_reserved_bits() {
+        let mut sync = ExtSync::from_bits(0xF8);
+        sync.set_mode(ExternalSyncMode::ExtSyncExtClockWithInterpolation);
+        assert_eq!(sync.to_bits(), 0xFE);
+        assert!(sync.ext_clk());
+        assert_eq!(sync.ext_sync(), 0x02);
+        assert_eq!(
+            sync.mode(),
+            Some(ExternalSyncMode::ExtSyncExtClockWithInterpolation)
+        );
+
+        assert!(ExtSync::from_bits(0x03).mode().is_none());
+    }
+
+    #[test]
+    fn range_setters_keep_the_reserved_bits() {
+        let mut range = Range::from_bits(0x3C);
+        range
+            .set_range(config::Range::_4G)
+            .set_i2c_speed(I2cSpeedMode::HighSpeed)
+            .set_interrupt_polarity(InterruptPolarity::ActiveHigh);
+        assert_eq!(range.to_bits(), 0xFE);
+        assert_eq!(range.range::<Adxl355Variant>(), Some(config::Range::_4G));
+        assert_eq!(range.range::<Adxl357Variant>(), Some(config::Range::_20G));
+
+        range
+            .set_i2c_speed(I2cSpeedMode::Fast)
+            .set_interrupt_polarity(InterruptPolarity::ActiveLow);
+        assert_eq!(range.to_bits(), 0x3E);
+        assert_eq!(range.i2c_speed(), I2cSpeedMode::Fast);
+        assert_eq!(range.interrupt_polarity(), InterruptPolarity::ActiveLow);
+
+        assert!(Range::from_bits(0x80).range::<Adxl355Variant>().is_none());
+    }
+
+    #[test]
+    fn act_en_and_self_test_setters_keep_the_reserved_bits() {
+        let mut act_en = ActEn::from_bits(0xF8);
+        act_en.set_x(true).set_z(true);
+        assert_eq!(act_en.to_bits(), 0xFD);
+        act_en.set_x(false).set_z(false);
+        assert_eq!(act_en.to_bits(), 0xF8);
+
+        let mut self_test = SelfTest::from_bits(0xF0);
+        self_test.set_st1(true).set_st2(true);
+        assert_eq!(self_test.to_bits(), 0xF3);
+        assert!(self_test.st1() && self_test.st2());
+    }
+
+    #[test]
+    fn read_only_values_mask_their_fields() {
+        assert_eq!(FifoEntries::from_bits(0xE0).entries(), 0x60);
+        assert_eq!(Temperature::from_bits(0xF7_FF).raw(), 0x07_FF);
+        assert_eq!(Temperature::from_bits(0xF7_FF).to_bits(), 0xF7_FF);
+    }
+
+    #[test]
+    fn offset_is_signed() {
+        let mut offset = OffsetX::from_bits(0xFF_FE);
+        assert_eq!(offset.offset(), -2);
+        offset.set_offset(300);
+        assert_eq!(offset.to_bits(), 0x01_2C);
+    }
+}
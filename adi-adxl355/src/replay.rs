@@ -0,0 +1,548 @@
+// Copyright © 2023 Analog Devices, Inc. All Rights Reserved. This software is
+// proprietary to Analog Devices, Inc. and its licensors.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Record-and-replay protocol backends, available with the `std` feature.
+//!
+//! [`RecordingProtocol`] writes the transactions made through a protocol as text, one per line
+//! with the direction, the start register address, the length, the bytes in hexadecimal (`-`
+//! for a failed read) and the result:
+//!
+//! ```text
+//! R 00 4 ad1ded01 ok
+//! W 2f 1 52 ok
+//! R 11 9 - err
+//! ```
+//!
+//! [`ReplayProtocol`] serves a recording back to the driver, checking that the driver issues
+//! the same transactions in the same order. Empty lines and lines starting with `#` are
+//! ignored.
+//!
+//! A capture made on hardware becomes a deterministic test:
+//!
+//! ```rust ignore
+//! // On the bench.
+//! let protocol = RecordingProtocol::new(SpiProtocol::new(spi_dev), File::create("fifo.txt")?);
+//! let mut acc = Adxl355::new_with_protocol(protocol, Adxl355Variant)?;
+//! acc.get_fifo_raw_data(&mut samples)?;
+//! acc.protocol.finish()?;
+//!
+//! // In the test.
+//! let protocol = ReplayProtocol::from_reader(BufReader::new(File::open("fifo.txt")?))?;
+//! let mut acc = Adxl355::new_with_protocol(protocol, Adxl355Variant)?;
+//! assert_eq!(acc.get_fifo_raw_data(&mut samples)?, 32);
+//! acc.protocol.finish()?;
+//! ```
+
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+use std::vec::Vec;
+
+use crate::register::Register;
+use crate::trace::Direction;
+use crate::{Error, Protocol};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+/// Transaction of a recording.
+pub struct RecordedTransaction {
+    /// Direction of the transaction.
+    pub direction: Direction,
+    /// Register the transaction started at.
+    pub register: Register,
+    /// Number of bytes transferred, or requested for a failed read.
+    pub len: usize,
+    /// Bytes transferred, empty for a failed read.
+    pub data: Vec<u8>,
+    /// Whether the transaction succeeded.
+    pub succeeded: bool,
+}
+
+impl RecordedTransaction {
+    /// Record a transaction of `data` starting at `register`.
+    /// The bytes of a failed read are not recorded.
+    pub fn new(register: Register, direction: Direction, data: &[u8], succeeded: bool) -> Self {
+        let recorded = succeeded || direction == Direction::Write;
+        RecordedTransaction {
+            direction,
+            register,
+            len: data.len(),
+            data: if recorded { data.to_vec() } else { Vec::new() },
+            succeeded,
+        }
+    }
+
+    /// Check if `other` is the same request: same direction, register and length,
+    /// and same bytes for a write.
+    fn same_request(&self, other: &RecordedTransaction) -> bool {
+        self.direction == other.direction
+            && self.register == other.register
+            && self.len == other.len
+            && (self.direction == Direction::Read || self.data == other.data)
+    }
+}
+
+impl fmt::Display for RecordedTransaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let direction = match self.direction {
+            Direction::Read => 'R',
+            Direction::Write => 'W',
+        };
+        write!(
+            f,
+            "{} {:02x} {} ",
+            direction,
+            self.register.addr(),
+            self.len
+        )?;
+        if self.data.is_empty() {
+            write!(f, "-")?;
+        }
+        for byte in &self.data {
+            write!(f, "{:02x}", byte)?;
+        }
+        write!(f, " {}", if self.succeeded { "ok" } else { "err" })
+    }
+}
+
+impl FromStr for RecordedTransaction {
+    type Err = &'static str;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut fields = line.split_whitespace();
+        let mut field = || fields.next().ok_or("missing field");
+
+        let direction = match field()? {
+            "R" => Direction::Read,
+            "W" => Direction::Write,
+            _ => return Err("invalid direction"),
+        };
+        let addr = u8::from_str_radix(field()?, 16).map_err(|_| "invalid register address")?;
+        let register = Register::try_from(addr).map_err(|_| "unknown register address")?;
+        let len = field()?.parse().map_err(|_| "invalid length")?;
+        let data = match field()? {
+            "-" => Vec::new(),
+            hex => parse_hex(hex)?,
+        };
+        let succeeded = match field()? {
+            "ok" => true,
+            "err" => false,
+            _ => return Err("invalid result"),
+        };
+
+        if fields.next().is_some() {
+            return Err("unexpected field");
+        }
+        if (succeeded || direction == Direction::Write) && data.len() != len {
+            return Err("length does not match the bytes");
+        }
+
+        Ok(RecordedTransaction {
+            direction,
+            register,
+            len,
+            data,
+            succeeded,
+        })
+    }
+}
+
+/// Parse a string of hexadecimal byte pairs.
+fn parse_hex(hex: &str) -> Result<Vec<u8>, &'static str> {
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return Err("invalid bytes");
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| "invalid bytes"))
+        .collect()
+}
+
+/// [`Protocol`] wrapper writing every transaction to `W` in the recording format.
+///
+/// Writing the recording does not interrupt the transactions, the first write error is kept
+/// and returned by [`RecordingProtocol::finish`].
+pub struct RecordingProtocol<P, W> {
+    protocol: P,
+    writer: W,
+    io_error: Option<io::Error>,
+}
+
+impl<P, W> RecordingProtocol<P, W>
+where
+    P: Protocol,
+    W: Write,
+{
+    /// Wrap `protocol`, recording to `writer`.
+    pub fn new(protocol: P, writer: W) -> Self {
+        RecordingProtocol {
+            protocol,
+            writer,
+            io_error: None,
+        }
+    }
+
+    /// Flush the recording and release the wrapped protocol and writer.
+    /// Returns the first error met while writing the recording.
+    pub fn finish(mut self) -> io::Result<(P, W)> {
+        if let Some(error) = self.io_error.take() {
+            return Err(error);
+        }
+        self.writer.flush()?;
+        Ok((self.protocol, self.writer))
+    }
+
+    /// Record a transaction.
+    fn record(&mut self, register: Register, direction: Direction, data: &[u8], succeeded: bool) {
+        if self.io_error.is_some() {
+            return;
+        }
+
+        let transaction = RecordedTransaction::new(register, direction, data, succeeded);
+        if let Err(error) = writeln!(self.writer, "{}", transaction) {
+            self.io_error = Some(error);
+        }
+    }
+}
+
+impl<P, W> Protocol for RecordingProtocol<P, W>
+where
+    P: Protocol,
+    W: Write,
+{
+    type ProtocolError = P::ProtocolError;
+
+    /// Read a single register.
+    fn read_register(&mut self, register: Register) -> Result<u8, Error<Self::ProtocolError>> {
+        let result = self.protocol.read_register(register);
+        let value = *result.as_ref().unwrap_or(&0);
+        self.record(register, Direction::Read, &[value], result.is_ok());
+        result
+    }
+
+    /// Read `buf.len()` registers from the `start_register` address included.
+    fn read_multiple_registers(
+        &mut self,
+        start_register: Register,
+        buf: &mut [u8],
+    ) -> Result<(), Error<Self::ProtocolError>> {
+        let result = self.protocol.read_multiple_registers(start_register, buf);
+        self.record(start_register, Direction::Read, buf, result.is_ok());
+        result
+    }
+
+    /// Write a single register.
+    fn write_register(
+        &mut self,
+        register: Register,
+        buf: u8,
+    ) -> Result<(), Error<Self::ProtocolError>> {
+        let result = self.protocol.write_register(register, buf);
+        self.record(register, Direction::Write, &[buf], result.is_ok());
+        result
+    }
+
+    /// Write `data.len()` registers from the `start_register` address included.
+    fn write_registers(
+        &mut self,
+        start_register: Register,
+        data: &[u8],
+    ) -> Result<(), Error<Self::ProtocolError>> {
+        let result = self.protocol.write_registers(start_register, data);
+        self.record(start_register, Direction::Write, data, result.is_ok());
+        result
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+/// Replay errors, returned as [`Error::Protocol`] by the [`ReplayProtocol`] transactions.
+pub enum ReplayError {
+    /// The driver issued a different transaction than the recorded one.
+    #[error("transaction {index}: expected `{expected}`, got `{actual}`")]
+    Mismatch {
+        index: usize,
+        expected: RecordedTransaction,
+        actual: RecordedTransaction,
+    },
+
+    /// The driver issued more transactions than recorded.
+    #[error("transaction {index}: the recording is exhausted")]
+    Exhausted { index: usize },
+
+    /// The recorded transaction failed.
+    #[error("transaction {index}: recorded failure")]
+    RecordedFailure { index: usize },
+
+    /// The driver did not issue all the recorded transactions.
+    #[error("{remaining} recorded transactions were not replayed")]
+    Unconsumed { remaining: usize },
+
+    /// A line of the recording is invalid.
+    #[error("line {line}: {reason}")]
+    Parse { line: usize, reason: &'static str },
+
+    /// The recording could not be read.
+    #[error("recording read error: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// [`Protocol`] serving a recording made by a [`RecordingProtocol`].
+///
+/// Each transaction must match the next recorded one, reads return the recorded bytes and
+/// recorded failures return [`ReplayError::RecordedFailure`].
+pub struct ReplayProtocol {
+    transactions: Vec<RecordedTransaction>,
+    next: usize,
+}
+
+impl ReplayProtocol {
+    /// Create a replay of the given transactions.
+    pub fn new(transactions: Vec<RecordedTransaction>) -> Self {
+        ReplayProtocol {
+            transactions,
+            next: 0,
+        }
+    }
+
+    /// Load a recording.
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, ReplayError> {
+        let mut transactions = Vec::new();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let transaction = line.parse().map_err(|reason| ReplayError::Parse {
+                line: index + 1,
+                reason,
+            })?;
+            transactions.push(transaction);
+        }
+
+        Ok(Self::new(transactions))
+    }
+
+    /// Number of recorded transactions left to replay.
+    pub fn remaining(&self) -> usize {
+        self.transactions.len() - self.next
+    }
+
+    /// Check that all the recorded transactions were replayed.
+    pub fn finish(self) -> Result<(), ReplayError> {
+        match self.remaining() {
+            0 => Ok(()),
+            remaining => Err(ReplayError::Unconsumed { remaining }),
+        }
+    }
+
+    /// Match `actual` against the next recorded transaction and return the recorded bytes.
+    fn replay(&mut self, actual: RecordedTransaction) -> Result<&[u8], Error<ReplayError>> {
+        let index = self.next;
+        let expected = self
+            .transactions
+            .get(index)
+            .ok_or(Error::Protocol(ReplayError::Exhausted { index }))?;
+
+        if !expected.same_request(&actual) {
+            return Err(Error::Protocol(ReplayError::Mismatch {
+                index,
+                expected: expected.clone(),
+                actual,
+            }));
+        }
+
+        self.next += 1;
+        if !expected.succeeded {
+            return Err(Error::Protocol(ReplayError::RecordedFailure { index }));
+        }
+        Ok(&expected.data)
+    }
+}
+
+impl Protocol for ReplayProtocol {
+    type ProtocolError = ReplayError;
+
+    /// Read a single register.
+    fn read_register(&mut self, register: Register) -> Result<u8, Error<Self::ProtocolError>> {
+        let mut buf = [0u8; 1];
+        self.read_multiple_registers(register, &mut buf)?;
+        Ok(buf[0])
+    }
+
+    /// Read `buf.len()` registers from the `start_register` address included.
+    fn read_multiple_registers(
+        &mut self,
+        start_register: Register,
+        buf: &mut [u8],
+    ) -> Result<(), Error<Self::ProtocolError>> {
+        let actual = RecordedTransaction {
+            direction: Direction::Read,
+            register: start_register,
+            len: buf.len(),
+            data: Vec::new(),
+            succeeded: true,
+        };
+        buf.copy_from_slice(self.replay(actual)?);
+        Ok(())
+    }
+
+    /// Write a single register.
+    fn write_register(
+        &mut self,
+        register: Register,
+        buf: u8,
+    ) -> Result<(), Error<Self::ProtocolError>> {
+        self.write_registers(register, &[buf])
+    }
+
+    /// Write `data.len()` registers from the `start_register` address included.
+    fn write_registers(
+        &mut self,
+        start_register: Register,
+        data: &[u8],
+    ) -> Result<(), Error<Self::ProtocolError>> {
+        let actual = RecordedTransaction::new(start_register, Direction::Write, data, true);
+        self.replay(actual)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RECORDING: &str = "\
+# identification, reset and a failed read
+R 00 4 ad1ded01 ok
+W 2f 1 52 ok
+
+R 05 1 - err
+";
+
+    fn replay() -> ReplayProtocol {
+        ReplayProtocol::from_reader(RECORDING.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn replays_the_recording() {
+        let mut protocol = replay();
+        assert_eq!(protocol.remaining(), 3);
+
+        let mut id = [0u8; 4];
+        protocol
+            .read_multiple_registers(Register::DEVID_AD, &mut id)
+            .unwrap();
+        assert_eq!(id, [0xAD, 0x1D, 0xED, 0x01]);
+        protocol.write_register(Register::RESET, 0x52).unwrap();
+        assert!(matches!(
+            protocol.read_register(Register::FIFO_ENTRIES),
+            Err(Error::Protocol(ReplayError::RecordedFailure { index: 2 }))
+        ));
+        assert_eq!(protocol.remaining(), 0);
+        protocol.finish().unwrap();
+    }
+
+    #[test]
+    fn reports_a_mismatch() {
+        let mut protocol = replay();
+        let mut id = [0u8; 4];
+        protocol
+            .read_multiple_registers(Register::DEVID_AD, &mut id)
+            .unwrap();
+
+        // Same register, different byte.
+        match protocol.write_register(Register::RESET, 0x00) {
+            Err(Error::Protocol(ReplayError::Mismatch {
+                index,
+                expected,
+                actual,
+            })) => {
+                assert_eq!(index, 1);
+                assert_eq!(expected.data, [0x52]);
+                assert_eq!(actual.data, [0x00]);
+            }
+            other => panic!("unexpected result {other:?}"),
+        }
+        // The mismatched transaction is not consumed.
+        assert_eq!(protocol.remaining(), 2);
+
+        // Different direction.
+        assert!(matches!(
+            protocol.read_register(Register::RESET),
+            Err(Error::Protocol(ReplayError::Mismatch { index: 1, .. }))
+        ));
+        // Different length.
+        let mut buf = [0u8; 2];
+        assert!(matches!(
+            ReplayProtocol::from_reader(RECORDING.as_bytes())
+                .unwrap()
+                .read_multiple_registers(Register::DEVID_AD, &mut buf),
+            Err(Error::Protocol(ReplayError::Mismatch { index: 0, .. }))
+        ));
+    }
+
+    #[test]
+    fn reports_exhaustion_and_unconsumed_transactions() {
+        let mut protocol = ReplayProtocol::from_reader("W 2f 1 52 ok".as_bytes()).unwrap();
+        protocol.write_register(Register::RESET, 0x52).unwrap();
+        assert!(matches!(
+            protocol.write_register(Register::RESET, 0x52),
+            Err(Error::Protocol(ReplayError::Exhausted { index: 1 }))
+        ));
+        protocol.finish().unwrap();
+
+        assert!(matches!(
+            replay().finish(),
+            Err(ReplayError::Unconsumed { remaining: 3 })
+        ));
+    }
+
+    #[test]
+    fn reports_invalid_lines() {
+        for (recording, line, reason) in [
+            ("R 00 1 ad ok\nX 00 1 ad ok", 2, "invalid direction"),
+            ("R 00 2 ad ok", 1, "length does not match the bytes"),
+            ("\nR ff 1 ad ok", 2, "unknown register address"),
+            ("R 00 1 ad", 1, "missing field"),
+        ] {
+            match ReplayProtocol::from_reader(recording.as_bytes()) {
+                Err(ReplayError::Parse {
+                    line: actual_line,
+                    reason: actual_reason,
+                }) => assert_eq!((actual_line, actual_reason), (line, reason)),
+                _ => panic!("{recording:?} parsed"),
+            }
+        }
+    }
+
+    #[test]
+    fn recording_a_replay_reproduces_it() {
+        let mut protocol = RecordingProtocol::new(replay(), Vec::new());
+        let mut id = [0u8; 4];
+        protocol
+            .read_multiple_registers(Register::DEVID_AD, &mut id)
+            .unwrap();
+        protocol.write_register(Register::RESET, 0x52).unwrap();
+        protocol.read_register(Register::FIFO_ENTRIES).unwrap_err();
+
+        let (replay, recording) = protocol.finish().unwrap();
+        replay.finish().unwrap();
+        assert_eq!(
+            std::str::from_utf8(&recording).unwrap(),
+            "R 00 4 ad1ded01 ok\nW 2f 1 52 ok\nR 05 1 - err\n"
+        );
+    }
+}
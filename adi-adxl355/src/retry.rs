@@ -0,0 +1,385 @@
+// Copyright © 2023 Analog Devices, Inc. All Rights Reserved. This software is
+// proprietary to Analog Devices, Inc. and its licensors.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::{i2c, spi};
+
+use crate::register::Register;
+use crate::{Error, Protocol};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// How a [`RetryingProtocol`] retries failed transactions.
+pub struct RetryPolicy {
+    /// Number of retries after the first attempt, 0 disables retrying.
+    pub retries: u8,
+    /// Delay before the first retry, in microseconds.
+    pub backoff_us: u32,
+    /// Factor applied to the delay after each retry, 1 keeps a constant delay.
+    pub backoff_factor: u32,
+}
+
+impl Default for RetryPolicy {
+    /// By default, 3 retries starting after 100 µs and doubling the delay each time.
+    fn default() -> Self {
+        RetryPolicy {
+            retries: 3,
+            backoff_us: 100,
+            backoff_factor: 2,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Bus error counters kept by a [`RetryingProtocol`], the counters saturate.
+pub struct BusStats {
+    /// Number of retries issued.
+    pub retries: u32,
+    /// Number of transactions that failed, after exhausting the retries or on an error
+    /// that is not retried.
+    pub failures: u32,
+    /// Number of transactions that succeeded after at least one retry.
+    pub recovered: u32,
+}
+
+/// Check if an I2C error is worth retrying: no acknowledge, arbitration loss, bus error
+/// and overrun are transient on long or noisy buses.
+pub fn is_transient_i2c_error(kind: i2c::ErrorKind) -> bool {
+    matches!(
+        kind,
+        i2c::ErrorKind::NoAcknowledge(_)
+            | i2c::ErrorKind::ArbitrationLoss
+            | i2c::ErrorKind::Bus
+            | i2c::ErrorKind::Overrun
+    )
+}
+
+/// Check if a SPI error is worth retrying: overrun, mode fault and chip select fault are
+/// transient, a frame format error is a configuration issue.
+pub fn is_transient_spi_error(kind: spi::ErrorKind) -> bool {
+    matches!(
+        kind,
+        spi::ErrorKind::Overrun | spi::ErrorKind::ModeFault | spi::ErrorKind::ChipSelectFault
+    )
+}
+
+/// Retry bookkeeping for a single transaction, shared by the blocking and async
+/// `RetryingProtocol`.
+#[derive(Copy, Clone, Debug)]
+pub struct RetryAttempt {
+    retries_left: u8,
+    backoff_us: u32,
+    backoff_factor: u32,
+    retried: bool,
+}
+
+impl RetryAttempt {
+    /// Start a transaction following `policy`.
+    pub fn new(policy: RetryPolicy) -> Self {
+        RetryAttempt {
+            retries_left: policy.retries,
+            backoff_us: policy.backoff_us,
+            backoff_factor: policy.backoff_factor,
+            retried: false,
+        }
+    }
+
+    /// Record a successful attempt.
+    pub fn succeeded(&self, stats: &mut BusStats) {
+        if self.retried {
+            stats.recovered = stats.recovered.saturating_add(1);
+        }
+    }
+
+    /// Record a failed attempt. Returns the delay to wait in microseconds before retrying,
+    /// or `None` if the error must be returned.
+    pub fn failed(&mut self, retryable: bool, stats: &mut BusStats) -> Option<u32> {
+        if !retryable || self.retries_left == 0 {
+            stats.failures = stats.failures.saturating_add(1);
+            return None;
+        }
+
+        self.retries_left -= 1;
+        self.retried = true;
+        stats.retries = stats.retries.saturating_add(1);
+
+        let backoff_us = self.backoff_us;
+        self.backoff_us = self.backoff_us.saturating_mul(self.backoff_factor);
+        Some(backoff_us)
+    }
+}
+
+/// [`Protocol`] wrapper retrying the transactions that fail with a transient bus error.
+///
+/// A failure in the middle of a FIFO_DATA read may already have popped entries from the FIFO,
+/// the retried read then starts from a later entry. The FIFO readers check the x-axis marker
+/// of every sample, and drop the misaligned one before resynchronizing on the next x-axis entry.
+pub struct RetryingProtocol<P: Protocol, D> {
+    protocol: P,
+    delay: D,
+    policy: RetryPolicy,
+    is_retryable: fn(&P::ProtocolError) -> bool,
+    stats: BusStats,
+}
+
+impl<P, D> RetryingProtocol<P, D>
+where
+    P: Protocol,
+    D: DelayNs,
+{
+    /// Wrap `protocol`, retrying the errors for which `is_retryable` returns true.
+    pub fn new(
+        protocol: P,
+        delay: D,
+        policy: RetryPolicy,
+        is_retryable: fn(&P::ProtocolError) -> bool,
+    ) -> Self {
+        RetryingProtocol {
+            protocol,
+            delay,
+            policy,
+            is_retryable,
+            stats: BusStats::default(),
+        }
+    }
+
+    /// Get the bus error counters.
+    pub fn stats(&self) -> BusStats {
+        self.stats
+    }
+
+    /// Reset the bus error counters.
+    pub fn reset_stats(&mut self) {
+        self.stats = BusStats::default();
+    }
+
+    /// Get the retry policy.
+    pub fn policy(&self) -> RetryPolicy {
+        self.policy
+    }
+
+    /// Change the retry policy.
+    pub fn set_policy(&mut self, policy: RetryPolicy) {
+        self.policy = policy;
+    }
+
+    /// Release the wrapped protocol and delay.
+    pub fn into_inner(self) -> (P, D) {
+        (self.protocol, self.delay)
+    }
+
+    /// Record a failed attempt and wait before the retry, or give back the error.
+    fn retry_after(
+        &mut self,
+        attempt: &mut RetryAttempt,
+        error: Error<P::ProtocolError>,
+    ) -> Result<(), Error<P::ProtocolError>> {
+        let retryable = matches!(&error, Error::Protocol(e) if (self.is_retryable)(e));
+        match attempt.failed(retryable, &mut self.stats) {
+            Some(backoff_us) => {
+                self.delay.delay_us(backoff_us);
+                Ok(())
+            }
+            None => Err(error),
+        }
+    }
+}
+
+impl<P, D> RetryingProtocol<P, D>
+where
+    P: Protocol,
+    P::ProtocolError: i2c::Error,
+    D: DelayNs,
+{
+    /// Wrap an I2C `protocol`, retrying the errors accepted by [`is_transient_i2c_error`].
+    pub fn for_i2c(protocol: P, delay: D, policy: RetryPolicy) -> Self {
+        Self::new(protocol, delay, policy, |error| {
+            is_transient_i2c_error(i2c::Error::kind(error))
+        })
+    }
+}
+
+impl<P, D> RetryingProtocol<P, D>
+where
+    P: Protocol,
+    P::ProtocolError: spi::Error,
+    D: DelayNs,
+{
+    /// Wrap a SPI `protocol`, retrying the errors accepted by [`is_transient_spi_error`].
+    pub fn for_spi(protocol: P, delay: D, policy: RetryPolicy) -> Self {
+        Self::new(protocol, delay, policy, |error| {
+            is_transient_spi_error(spi::Error::kind(error))
+        })
+    }
+}
+
+impl<P, D> Protocol for RetryingProtocol<P, D>
+where
+    P: Protocol,
+    D: DelayNs,
+{
+    type ProtocolError = P::ProtocolError;
+
+    /// Read a single register.
+    fn read_register(&mut self, register: Register) -> Result<u8, Error<Self::ProtocolError>> {
+        let mut attempt = RetryAttempt::new(self.policy);
+        loop {
+            match self.protocol.read_register(register) {
+                Ok(value) => {
+                    attempt.succeeded(&mut self.stats);
+                    return Ok(value);
+                }
+                Err(error) => self.retry_after(&mut attempt, error)?,
+            }
+        }
+    }
+
+    /// Read `buf.len()` registers from the `start_register` address included.
+    fn read_multiple_registers(
+        &mut self,
+        start_register: Register,
+        buf: &mut [u8],
+    ) -> Result<(), Error<Self::ProtocolError>> {
+        let mut attempt = RetryAttempt::new(self.policy);
+        loop {
+            match self.protocol.read_multiple_registers(start_register, buf) {
+                Ok(()) => {
+                    attempt.succeeded(&mut self.stats);
+                    return Ok(());
+                }
+                Err(error) => self.retry_after(&mut attempt, error)?,
+            }
+        }
+    }
+
+    /// Write a single register.
+    fn write_register(
+        &mut self,
+        register: Register,
+        buf: u8,
+    ) -> Result<(), Error<Self::ProtocolError>> {
+        let mut attempt = RetryAttempt::new(self.policy);
+        loop {
+            match self.protocol.write_register(register, buf) {
+                Ok(()) => {
+                    attempt.succeeded(&mut self.stats);
+                    return Ok(());
+                }
+                Err(error) => self.retry_after(&mut attempt, error)?,
+            }
+        }
+    }
+
+    /// Write `data.len()` registers from the `start_register` address included.
+    fn write_registers(
+        &mut self,
+        start_register: Register,
+        data: &[u8],
+    ) -> Result<(), Error<Self::ProtocolError>> {
+        let mut attempt = RetryAttempt::new(self.policy);
+        loop {
+            match self.protocol.write_registers(start_register, data) {
+                Ok(()) => {
+                    attempt.succeeded(&mut self.stats);
+                    return Ok(());
+                }
+                Err(error) => self.retry_after(&mut attempt, error)?,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_attempt_backs_off_until_exhausted() {
+        let mut stats = BusStats::default();
+        let mut attempt = RetryAttempt::new(RetryPolicy::default());
+        assert_eq!(attempt.failed(true, &mut stats), Some(100));
+        assert_eq!(attempt.failed(true, &mut stats), Some(200));
+        assert_eq!(attempt.failed(true, &mut stats), Some(400));
+        assert_eq!(attempt.failed(true, &mut stats), None);
+        assert_eq!(
+            stats,
+            BusStats {
+                retries: 3,
+                failures: 1,
+                recovered: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn retry_attempt_counts_recoveries() {
+        let mut stats = BusStats::default();
+        let mut attempt = RetryAttempt::new(RetryPolicy::default());
+        attempt.succeeded(&mut stats);
+        assert_eq!(stats.recovered, 0);
+
+        assert_eq!(attempt.failed(true, &mut stats), Some(100));
+        attempt.succeeded(&mut stats);
+        assert_eq!(
+            stats,
+            BusStats {
+                retries: 1,
+                failures: 0,
+                recovered: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn retry_attempt_gives_up_on_errors_not_retried() {
+        let mut stats = BusStats::default();
+        let mut attempt = RetryAttempt::new(RetryPolicy::default());
+        assert_eq!(attempt.failed(false, &mut stats), None);
+        assert_eq!(stats.retries, 0);
+        assert_eq!(stats.failures, 1);
+
+        let policy = RetryPolicy {
+            retries: 0,
+            ..Default::default()
+        };
+        assert_eq!(RetryAttempt::new(policy).failed(true, &mut stats), None);
+        assert_eq!(stats.failures, 2);
+    }
+
+    #[test]
+    fn retry_attempt_saturates_the_backoff() {
+        let mut stats = BusStats::default();
+        let policy = RetryPolicy {
+            retries: 3,
+            backoff_us: u32::MAX / 2,
+            backoff_factor: 4,
+        };
+        let mut attempt = RetryAttempt::new(policy);
+        assert_eq!(attempt.failed(true, &mut stats), Some(u32::MAX / 2));
+        assert_eq!(attempt.failed(true, &mut stats), Some(u32::MAX));
+        assert_eq!(attempt.failed(true, &mut stats), Some(u32::MAX));
+    }
+
+    #[test]
+    fn transient_errors() {
+        assert!(is_transient_i2c_error(i2c::ErrorKind::NoAcknowledge(
+            i2c::NoAcknowledgeSource::Address
+        )));
+        assert!(is_transient_i2c_error(i2c::ErrorKind::ArbitrationLoss));
+        assert!(!is_transient_i2c_error(i2c::ErrorKind::Other));
+        assert!(is_transient_spi_error(spi::ErrorKind::ModeFault));
+        assert!(!is_transient_spi_error(spi::ErrorKind::FrameFormat));
+    }
+}
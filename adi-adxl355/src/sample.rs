@@ -0,0 +1,74 @@
+// Copyright © 2023 Analog Devices, Inc. All Rights Reserved. This software is
+// proprietary to Analog Devices, Inc. and its licensors.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Samples with plain fields, serialized with the `serde` feature.
+//!
+//! The drivers return the samples as micromath vectors, raw in LSB as [`I32x3`] and in g as
+//! [`F32x3`]. [`Sample`] holds the same three axes and converts from and to both, so that raw
+//! and scaled samples can be serialized, e.g. emitted as JSON, without the `units` feature.
+
+use micromath::vector::{F32x3, I32x3};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Sample on the three axes, raw in LSB as `Sample<i32>` or in g as `Sample<f32>`.
+pub struct Sample<T> {
+    /// Acceleration on the x-axis.
+    pub x: T,
+    /// Acceleration on the y-axis.
+    pub y: T,
+    /// Acceleration on the z-axis.
+    pub z: T,
+}
+
+impl From<I32x3> for Sample<i32> {
+    fn from(sample: I32x3) -> Self {
+        Sample {
+            x: sample.x,
+            y: sample.y,
+            z: sample.z,
+        }
+    }
+}
+
+impl From<Sample<i32>> for I32x3 {
+    fn from(sample: Sample<i32>) -> Self {
+        I32x3 {
+            x: sample.x,
+            y: sample.y,
+            z: sample.z,
+        }
+    }
+}
+
+impl From<F32x3> for Sample<f32> {
+    fn from(sample: F32x3) -> Self {
+        Sample {
+            x: sample.x,
+            y: sample.y,
+            z: sample.z,
+        }
+    }
+}
+
+impl From<Sample<f32>> for F32x3 {
+    fn from(sample: Sample<f32>) -> Self {
+        F32x3 {
+            x: sample.x,
+            y: sample.y,
+            z: sample.z,
+        }
+    }
+}
@@ -0,0 +1,631 @@
+// Copyright © 2023 Analog Devices, Inc. All Rights Reserved. This software is
+// proprietary to Analog Devices, Inc. and its licensors.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Transport independent logic shared by the blocking and async drivers.
+//!
+//! Register encoding and decoding, the soft reset sequence, the FIFO parser and the
+//! configuration sequence live here as plain data transformations and state machines.
+//! The drivers only execute the resulting register operations over their bus.
+
+use core::ops::Range as BufRange;
+
+use micromath::vector::{F32x3, I32x3};
+
+use crate::config::{Config, HpfCorner, Odr, ScaleRatio};
+use crate::interrupt::InterruptPolarity;
+use crate::modes::{I2cSpeedMode, Mode};
+use crate::register::Register;
+use crate::regs::{self, ActEn, FifoEntries, Filter, RegisterValue, Temperature};
+use crate::status::Status;
+use crate::variant::Variant;
+use crate::Error;
+
+/// Code to write in the [`Register::RESET`] register to reset the device.
+pub const RESET_CODE: u8 = 0x52;
+
+/// Maximum number of FIFO entries, 32 samples of 3 axes.
+pub const FIFO_MAX_ENTRIES: u8 = 96;
+
+/// Time between two polls of the data ready status or DRDY pin, a fifth of the shortest ODR period.
+pub const DATA_READY_POLL_INTERVAL_US: u32 = 50;
+
+const FIFO_X_MARKER_BIT: u8 = 0x01;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Register operation a driver has to execute.
+pub enum RegisterOp {
+    /// Write the value to the register.
+    Write(Register, u8),
+    /// Read the register, replace the `mask` bits with the ones of `value` and write it back.
+    Update {
+        register: Register,
+        mask: u8,
+        value: u8,
+    },
+}
+
+impl RegisterOp {
+    /// Value to write back for an [`RegisterOp::Update`] given the current register value.
+    pub fn updated_value(mask: u8, value: u8, current: u8) -> u8 {
+        (current & !mask) | (value & mask)
+    }
+}
+
+/// Check that the [`Config`] can be applied to the [`Variant`].
+pub fn validate_config<VARIANT: Variant, E: core::fmt::Debug>(
+    config: &Config,
+) -> Result<(), Error<E>> {
+    if VARIANT::supports(config.range) {
+        Ok(())
+    } else {
+        Err(Error::UnsupportedRange)
+    }
+}
+
+/// Check that the device is in standby mode and can be configured.
+pub fn check_standby<E: core::fmt::Debug>(mode: Mode) -> Result<(), Error<E>> {
+    if mode.is_in_standby() {
+        Ok(())
+    } else {
+        Err(Error::DeviceRunning)
+    }
+}
+
+/// Register operations applying a [`Config`], the device must be in standby mode.
+pub fn config_ops(config: &Config) -> [RegisterOp; 2] {
+    [
+        RegisterOp::Write(Register::FILTER, encode_filter(config.odr, config.hpf)),
+        RegisterOp::Update {
+            register: Register::RANGE,
+            mask: regs::Range::RANGE_BITS,
+            value: config.range.val(),
+        },
+    ]
+}
+
+/// Register operation setting or clearing the `mask` bits of the POWER_CTL register.
+pub fn power_ctl_op(mask: u8, set: bool) -> RegisterOp {
+    RegisterOp::Update {
+        register: Register::POWER_CTL,
+        mask,
+        value: if set { mask } else { 0 },
+    }
+}
+
+/// Register operation setting the interrupt pins polarity.
+pub fn interrupt_polarity_op(polarity: InterruptPolarity) -> RegisterOp {
+    RegisterOp::Update {
+        register: Register::RANGE,
+        mask: regs::Range::INT_POL_BIT,
+        value: match polarity {
+            InterruptPolarity::ActiveHigh => regs::Range::INT_POL_BIT,
+            InterruptPolarity::ActiveLow => 0,
+        },
+    }
+}
+
+/// Register operation setting the I2C speed mode.
+pub fn i2c_speed_op(mode: I2cSpeedMode) -> RegisterOp {
+    RegisterOp::Update {
+        register: Register::RANGE,
+        mask: regs::Range::I2C_HS_BIT,
+        value: match mode {
+            I2cSpeedMode::HighSpeed => regs::Range::I2C_HS_BIT,
+            I2cSpeedMode::Fast => 0,
+        },
+    }
+}
+
+/// Encode the [`Register::FILTER`] register value.
+pub fn encode_filter(odr: Odr, hpf: HpfCorner) -> u8 {
+    Filter::default().set_odr(odr).set_hpf_corner(hpf).to_bits()
+}
+
+/// Encode the [`Register::ACT_EN`] register value.
+pub fn encode_activity_enable(x: bool, y: bool, z: bool) -> u8 {
+    ActEn::default().set_x(x).set_y(y).set_z(z).to_bits()
+}
+
+/// Encode a 16-bit value as its high and low register bytes.
+pub fn encode_u16(value: u16) -> [u8; 2] {
+    value.to_be_bytes()
+}
+
+/// Encode the offset trims as the [`Register::OFFSET_X_H`] to [`Register::OFFSET_Z_L`] values.
+pub fn encode_offsets(offset_x: i16, offset_y: i16, offset_z: i16) -> [u8; 6] {
+    let [x_h, x_l] = offset_x.to_be_bytes();
+    let [y_h, y_l] = offset_y.to_be_bytes();
+    let [z_h, z_l] = offset_z.to_be_bytes();
+    [x_h, x_l, y_h, y_l, z_h, z_l]
+}
+
+/// Check the maximum number of samples the FIFO will store.
+pub fn validate_fifo_samples<E: core::fmt::Debug>(fifo_samples: u8) -> Result<(), Error<E>> {
+    if (1..=FIFO_MAX_ENTRIES).contains(&fifo_samples) {
+        Ok(())
+    } else {
+        Err(Error::InvalidMaxNbFifoSamples)
+    }
+}
+
+/// Decode the [`Register::FIFO_ENTRIES`] register value.
+pub fn decode_fifo_entries(value: u8) -> u8 {
+    FifoEntries::from_bits(value).entries()
+}
+
+/// Number of complete 3-axis samples held by the given number of FIFO entries.
+pub fn complete_samples(fifo_entries: u8) -> u8 {
+    fifo_entries / 3
+}
+
+/// Decode the [`Register::TEMP2`] and [`Register::TEMP1`] register values.
+pub fn decode_temperature_raw(buf: &[u8; 2]) -> u16 {
+    Temperature::from_bits(u16::from_be_bytes(*buf)).raw()
+}
+
+/// Convert a raw temperature value to Celsius.
+pub fn temperature_celsius(raw_temp: u16) -> f32 {
+    ((((raw_temp as i32) - 1885i32) as f32) / (-9.05f32)) + 25.0f32
+}
+
+/// Convert a raw temperature value to milli-degrees Celsius, with integer arithmetic only.
+pub fn temperature_milli_celsius(raw_temp: u16) -> i32 {
+    ScaleRatio::TEMPERATURE_MILLI_CELSIUS_PER_LSB.scale(1885 - raw_temp as i32) + 25_000
+}
+
+/// Check that temperature processing is enabled so the temperature registers are up to date.
+pub fn check_temperature_enabled<E: core::fmt::Debug>(mode: Mode) -> Result<(), Error<E>> {
+    if mode.is_temperature_off() {
+        Err(Error::TemperatureDisabled)
+    } else {
+        Ok(())
+    }
+}
+
+/// Decode 9 bytes of acceleration data (x, y and z, 3 bytes each) to a raw sample.
+pub fn decode_sample(buf: &[u8; 9]) -> I32x3 {
+    I32x3 {
+        x: decode_axis(buf[0], buf[1], buf[2]),
+        y: decode_axis(buf[3], buf[4], buf[5]),
+        z: decode_axis(buf[6], buf[7], buf[8]),
+    }
+}
+
+/// Decode a left justified, twos complement, 20-bit axis value.
+fn decode_axis(data3: u8, data2: u8, data1: u8) -> i32 {
+    (((data3 as i32) << 24) | ((data2 as i32) << 16) | ((data1 & 0xF0) as i32) << 8) >> 12
+}
+
+/// Scale a raw sample to g using the range scale factor.
+pub fn scale_sample(raw: I32x3, scale_factor: f32) -> F32x3 {
+    F32x3 {
+        x: raw.x as f32 * scale_factor,
+        y: raw.y as f32 * scale_factor,
+        z: raw.z as f32 * scale_factor,
+    }
+}
+
+/// Scale a raw sample with a [`ScaleRatio`], with integer arithmetic only.
+pub fn scale_sample_ratio(raw: I32x3, ratio: ScaleRatio) -> I32x3 {
+    I32x3 {
+        x: ratio.scale(raw.x),
+        y: ratio.scale(raw.y),
+        z: ratio.scale(raw.z),
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Next action of the [`SoftReset`] sequence.
+pub enum ResetAction {
+    /// Write [`RESET_CODE`] to [`Register::RESET`].
+    WriteReset,
+    /// Wait the given number of microseconds, then call [`SoftReset::waited`].
+    Wait(u32),
+    /// Read the [`Register::STATUS`] register.
+    ReadStatus,
+    /// Read the 5 shadow registers from [`Register::SHADOW_REG1`].
+    ReadShadowRegisters,
+    /// The reset sequence succeeded.
+    Done,
+    /// The reset sequence failed.
+    Failed,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Soft reset sequence: reset the device, wait for the NVM to be loaded and check that the
+/// shadow registers hold the values read when the driver was created.
+pub struct SoftReset {
+    action: ResetAction,
+    expected_shadow_values: [u8; 5],
+    polls_left: u32,
+    poll_interval_us: u32,
+}
+
+impl SoftReset {
+    /// Maximum number of times the NVM busy flag is polled by a sequence without delay.
+    pub const MAX_POLLS: u8 = 255;
+
+    /// Time between two polls of the NVM busy flag by a sequence with a timeout.
+    pub const POLL_INTERVAL_US: u32 = 100;
+
+    /// Default time allowed for the NVM to be loaded after the reset.
+    pub const DEFAULT_TIMEOUT_US: u32 = 10_000;
+
+    /// Start a reset sequence polling the NVM busy flag [`Self::MAX_POLLS`] times back to back.
+    pub fn new(expected_shadow_values: [u8; 5]) -> Self {
+        SoftReset {
+            action: ResetAction::WriteReset,
+            expected_shadow_values,
+            polls_left: Self::MAX_POLLS.into(),
+            poll_interval_us: 0,
+        }
+    }
+
+    /// Start a reset sequence waiting [`Self::POLL_INTERVAL_US`] between the polls of the NVM
+    /// busy flag, and failing when it is still set after `timeout_us`.
+    pub fn with_timeout(expected_shadow_values: [u8; 5], timeout_us: u32) -> Self {
+        SoftReset {
+            action: ResetAction::WriteReset,
+            expected_shadow_values,
+            polls_left: timeout_us / Self::POLL_INTERVAL_US,
+            poll_interval_us: Self::POLL_INTERVAL_US,
+        }
+    }
+
+    /// Next action to execute.
+    pub fn action(&self) -> ResetAction {
+        self.action
+    }
+
+    /// The reset code was written.
+    pub fn reset_written(&mut self) {
+        self.action = self.next_poll();
+    }
+
+    /// The delay requested by [`ResetAction::Wait`] elapsed.
+    pub fn waited(&mut self) {
+        self.action = ResetAction::ReadStatus;
+    }
+
+    /// The status register was read.
+    pub fn status_read(&mut self, status: Status) {
+        self.action = if !status.is_nvm_busy() {
+            ResetAction::ReadShadowRegisters
+        } else if self.polls_left == 0 {
+            ResetAction::Failed
+        } else {
+            self.polls_left -= 1;
+            self.next_poll()
+        };
+    }
+
+    /// The shadow registers were read.
+    pub fn shadow_registers_read(&mut self, shadow_values: [u8; 5]) {
+        self.action = if shadow_values == self.expected_shadow_values {
+            ResetAction::Done
+        } else {
+            ResetAction::Failed
+        };
+    }
+
+    /// Poll the status register, after a delay if the sequence has a timeout.
+    fn next_poll(&self) -> ResetAction {
+        if self.poll_interval_us == 0 {
+            ResetAction::ReadStatus
+        } else {
+            ResetAction::Wait(self.poll_interval_us)
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+enum FifoState {
+    /// Looking for the x-axis marker, one entry at a time.
+    Sync,
+    /// x-axis entry found, the y and z entries complete the first sample.
+    FirstSample,
+    /// Aligned on samples, 3 entries at a time.
+    Samples,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// FIFO parser turning [`Register::FIFO_DATA`] reads into samples.
+///
+/// The parser skips entries until the first x-axis entry, then reads complete samples as long
+/// as the FIFO holds some and there is room left for them. A sample not starting with an x-axis
+/// entry is dropped and the parser skips entries again until the next one. The driver reads the
+/// bytes designated by [`FifoParser::next_read`] into a 9-byte buffer, then hands the buffer to
+/// [`FifoParser::parse`].
+pub struct FifoParser {
+    state: FifoState,
+    entries_left: u8,
+    capacity: usize,
+    retrieved: usize,
+    discarded: u8,
+}
+
+impl FifoParser {
+    /// Create a parser for the given number of FIFO entries and room for `capacity` samples.
+    pub fn new<E: core::fmt::Debug>(fifo_entries: u8, capacity: usize) -> Result<Self, Error<E>> {
+        if fifo_entries < 3 {
+            return Err(Error::NotEnoughData);
+        }
+
+        Ok(FifoParser {
+            state: FifoState::Sync,
+            entries_left: fifo_entries,
+            capacity,
+            retrieved: 0,
+            discarded: 0,
+        })
+    }
+
+    /// Part of the 9-byte buffer to read from the FIFO next, `None` when parsing is done.
+    pub fn next_read(&self) -> Option<BufRange<usize>> {
+        if self.retrieved >= self.capacity {
+            return None;
+        }
+
+        match self.state {
+            FifoState::Sync => Some(0..3),
+            FifoState::FirstSample => Some(3..9),
+            FifoState::Samples if self.entries_left >= 3 => Some(0..9),
+            FifoState::Samples => None,
+        }
+    }
+
+    /// Parse the buffer after the read designated by [`FifoParser::next_read`].
+    /// Returns the sample it completes, if any.
+    pub fn parse<E: core::fmt::Debug>(&mut self, buf: &[u8; 9]) -> Result<Option<I32x3>, Error<E>> {
+        match self.state {
+            FifoState::Sync => {
+                self.entries_left -= 1;
+                if buf[2] & FIFO_X_MARKER_BIT != 0 && self.entries_left >= 2 {
+                    self.state = FifoState::FirstSample;
+                    return Ok(None);
+                }
+                // Not an x-axis entry, or one without its y and z entries.
+                self.discarded += 1;
+                if self.entries_left < 3 {
+                    if self.retrieved == 0 {
+                        return Err(Error::NotEnoughData);
+                    }
+                    self.state = FifoState::Samples;
+                }
+                Ok(None)
+            }
+            FifoState::FirstSample => {
+                self.entries_left -= 2;
+                self.state = FifoState::Samples;
+                self.retrieved += 1;
+                Ok(Some(decode_sample(buf)))
+            }
+            FifoState::Samples => {
+                self.entries_left -= 3;
+                if buf[2] & FIFO_X_MARKER_BIT == 0 {
+                    // An entry was lost, resynchronize on the next x-axis entry.
+                    self.discarded += 3;
+                    if self.entries_left >= 3 {
+                        self.state = FifoState::Sync;
+                    }
+                    return Ok(None);
+                }
+                self.retrieved += 1;
+                Ok(Some(decode_sample(buf)))
+            }
+        }
+    }
+
+    /// Number of samples retrieved so far.
+    pub fn retrieved(&self) -> usize {
+        self.retrieved
+    }
+
+    /// Number of entries dropped so far because they did not belong to a complete sample.
+    pub fn discarded(&self) -> u8 {
+        self.discarded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHADOW: [u8; 5] = [0x11, 0x22, 0x33, 0x44, 0x55];
+
+    /// FIFO entry holding `value` on one axis, flagged as x-axis entry when `x` is set.
+    fn entry(value: i32, x: bool) -> [u8; 3] {
+        let bytes = ((value as u32) << 12).to_be_bytes();
+        [bytes[0], bytes[1], bytes[2] | u8::from(x)]
+    }
+
+    /// The three entries of a sample whose axes hold `x`, `x + 1` and `x + 2`.
+    fn sample(x: i32) -> [[u8; 3]; 3] {
+        [entry(x, true), entry(x + 1, false), entry(x + 2, false)]
+    }
+
+    /// Run `parser` over `fifo` as the drivers do, storing the samples in `samples`.
+    fn run(
+        parser: &mut FifoParser,
+        fifo: &[[u8; 3]],
+        samples: &mut [I32x3],
+    ) -> Result<usize, Error<()>> {
+        let mut fifo = fifo.iter();
+        let mut buf = [0u8; 9];
+        let mut reads = 0;
+        while let Some(range) = parser.next_read() {
+            for chunk in buf[range].chunks_mut(3) {
+                chunk.copy_from_slice(fifo.next().unwrap());
+                reads += 1;
+            }
+            if let Some(sample) = parser.parse(&buf)? {
+                samples[parser.retrieved() - 1] = sample;
+            }
+        }
+        Ok(reads)
+    }
+
+    fn parse(fifo: &[[u8; 3]], capacity: usize) -> (FifoParser, [I32x3; 4], usize) {
+        let mut parser = FifoParser::new::<()>(fifo.len() as u8, capacity).unwrap();
+        let mut samples = [I32x3::default(); 4];
+        let reads = run(&mut parser, fifo, &mut samples).unwrap();
+        (parser, samples, reads)
+    }
+
+    #[test]
+    fn fifo_parser_reads_aligned_samples() {
+        let fifo = [sample(-2), sample(10), sample(0x7FFFD)].concat();
+        let (parser, samples, reads) = parse(&fifo, 4);
+        assert_eq!(parser.retrieved(), 3);
+        assert_eq!(parser.discarded(), 0);
+        assert_eq!(reads, 9);
+        assert_eq!((samples[0].x, samples[0].y, samples[0].z), (-2, -1, 0));
+        assert_eq!((samples[1].x, samples[1].y, samples[1].z), (10, 11, 12));
+        assert_eq!(samples[2].z, 0x7FFFF);
+    }
+
+    #[test]
+    fn fifo_parser_stops_at_capacity() {
+        let fifo = [sample(1), sample(4), sample(7)].concat();
+        let (parser, samples, reads) = parse(&fifo, 2);
+        assert_eq!(parser.retrieved(), 2);
+        assert_eq!(parser.discarded(), 0);
+        assert_eq!(reads, 6);
+        assert_eq!(samples[1].x, 4);
+    }
+
+    #[test]
+    fn fifo_parser_skips_to_the_first_x_axis_entry() {
+        let head = sample(1);
+        let fifo = [&head[1..], &sample(4)[..], &sample(7)[..]].concat();
+        let (parser, samples, _) = parse(&fifo, 4);
+        assert_eq!(parser.retrieved(), 2);
+        assert_eq!(parser.discarded(), 2);
+        assert_eq!(samples[0].x, 4);
+        assert_eq!(samples[1].x, 7);
+    }
+
+    #[test]
+    fn fifo_parser_resyncs_after_a_lost_entry() {
+        // The x-axis entry of the second sample is lost, the next read is misaligned.
+        let second = sample(4);
+        let fifo = [
+            &sample(1)[..],
+            &second[1..],
+            &sample(7)[..],
+            &sample(10)[..],
+        ]
+        .concat();
+        let (parser, samples, reads) = parse(&fifo, 4);
+        // The misaligned read drops the y and z entries of the second sample along with the
+        // x-axis entry of the third, then the rest of the third is skipped.
+        assert_eq!(parser.retrieved(), 2);
+        assert_eq!(parser.discarded(), 5);
+        assert_eq!(reads, fifo.len());
+        assert_eq!(samples[0].x, 1);
+        assert_eq!(samples[1].x, 10);
+    }
+
+    #[test]
+    fn fifo_parser_reports_not_enough_data() {
+        assert!(matches!(
+            FifoParser::new::<()>(2, 4),
+            Err(Error::NotEnoughData)
+        ));
+
+        // No x-axis entry with its y and z entries behind it.
+        let first = sample(1);
+        for fifo in [
+            [first[1], first[2], first[1]],
+            [first[1], first[0], first[1]],
+        ] {
+            let mut parser = FifoParser::new::<()>(3, 4).unwrap();
+            let mut samples = [I32x3::default(); 4];
+            assert!(matches!(
+                run(&mut parser, &fifo, &mut samples),
+                Err(Error::NotEnoughData)
+            ));
+            assert_eq!(parser.retrieved(), 0);
+            assert_eq!(parser.discarded(), 1);
+        }
+    }
+
+    #[test]
+    fn fifo_parser_keeps_the_samples_before_a_trailing_partial_one() {
+        let fifo = [&sample(1)[..], &sample(4)[..2]].concat();
+        let (parser, samples, reads) = parse(&fifo, 4);
+        assert_eq!(parser.retrieved(), 1);
+        assert_eq!(parser.discarded(), 0);
+        assert_eq!(reads, 3);
+        assert_eq!(samples[0].x, 1);
+    }
+
+    #[test]
+    fn soft_reset_succeeds() {
+        let mut reset = SoftReset::new(SHADOW);
+        assert_eq!(reset.action(), ResetAction::WriteReset);
+        reset.reset_written();
+        assert_eq!(reset.action(), ResetAction::ReadStatus);
+        reset.status_read(Status(Status::NVM_BUSY_BIT));
+        assert_eq!(reset.action(), ResetAction::ReadStatus);
+        reset.status_read(Status(0));
+        assert_eq!(reset.action(), ResetAction::ReadShadowRegisters);
+        reset.shadow_registers_read(SHADOW);
+        assert_eq!(reset.action(), ResetAction::Done);
+    }
+
+    #[test]
+    fn soft_reset_fails_on_shadow_mismatch() {
+        let mut reset = SoftReset::new(SHADOW);
+        reset.reset_written();
+        reset.status_read(Status(0));
+        reset.shadow_registers_read([0x11, 0x22, 0x33, 0x44, 0x00]);
+        assert_eq!(reset.action(), ResetAction::Failed);
+    }
+
+    #[test]
+    fn soft_reset_fails_after_max_polls() {
+        let mut reset = SoftReset::new(SHADOW);
+        reset.reset_written();
+        for _ in 0..SoftReset::MAX_POLLS {
+            reset.status_read(Status(Status::NVM_BUSY_BIT));
+            assert_eq!(reset.action(), ResetAction::ReadStatus);
+        }
+        reset.status_read(Status(Status::NVM_BUSY_BIT));
+        assert_eq!(reset.action(), ResetAction::Failed);
+    }
+
+    #[test]
+    fn soft_reset_waits_until_the_timeout() {
+        let mut reset = SoftReset::with_timeout(SHADOW, 3 * SoftReset::POLL_INTERVAL_US);
+        reset.reset_written();
+        for _ in 0..3 {
+            assert_eq!(
+                reset.action(),
+                ResetAction::Wait(SoftReset::POLL_INTERVAL_US)
+            );
+            reset.waited();
+            assert_eq!(reset.action(), ResetAction::ReadStatus);
+            reset.status_read(Status(Status::NVM_BUSY_BIT));
+        }
+        reset.waited();
+        reset.status_read(Status(Status::NVM_BUSY_BIT));
+        assert_eq!(reset.action(), ResetAction::Failed);
+    }
+}
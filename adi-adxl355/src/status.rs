@@ -14,6 +14,7 @@
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// ADXL355 status providing its various conditions.
 pub struct Status(pub u8);
 
@@ -0,0 +1,237 @@
+// Copyright © 2023 Analog Devices, Inc. All Rights Reserved. This software is
+// proprietary to Analog Devices, Inc. and its licensors.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! External synchronization and clock.
+//!
+//! With the external clock on pin INT2, the ODR and the filter corners scale with the clock
+//! frequency. With external synchronization, pin DRDY becomes the input of the sync pulses and
+//! a sample is output on each pulse, so DRDY can no longer signal data ready. [`ExternalSync`]
+//! computes the rates the device actually runs at.
+
+use crate::config::{HpfCorner, Odr};
+use crate::modes::ExternalSyncMode;
+
+/// Internal clock frequency, the ODRs of [`Odr`] are specified for an external clock at this
+/// frequency.
+pub const NOMINAL_CLOCK_HZ: u32 = 1_024_000;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
+/// Invalid external synchronization configuration.
+pub enum ExternalSyncError {
+    /// The external clock frequency is 0 or runs the device above the fastest ODR.
+    #[cfg_attr(feature = "std", error("external clock {0} Hz out of range"))]
+    ClockOutOfRange(u32),
+    /// The sync pulse rate, in millihertz, is 0 or above the ODR the device runs at.
+    #[cfg_attr(feature = "std", error("sync rate {0} mHz out of range"))]
+    SyncRateOutOfRange(u32),
+    /// The SYNC register holds the reserved EXT_SYNC setting `0b11`.
+    #[cfg_attr(feature = "std", error("reserved synchronization mode 0x{0:02X}"))]
+    ReservedMode(u8),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// External synchronization configuration: the [`ExternalSyncMode`], the frequency of the
+/// external clock and the rate of the sync pulses.
+pub struct ExternalSync {
+    /// Synchronization mode written to the SYNC register.
+    pub mode: ExternalSyncMode,
+    /// Frequency of the clock on pin INT2, used by the modes with the external clock.
+    pub clock_hz: u32,
+    /// Rate of the pulses on pin DRDY in Hertz, used by the modes with external
+    /// synchronization. `None` when the pulses come at the ODR the device runs at.
+    pub sync_hz: Option<f32>,
+}
+
+impl ExternalSync {
+    /// Creates a configuration for the given mode, with the external clock at
+    /// [`NOMINAL_CLOCK_HZ`] and the sync pulses at the ODR.
+    pub fn new(mode: ExternalSyncMode) -> Self {
+        ExternalSync {
+            mode,
+            clock_hz: NOMINAL_CLOCK_HZ,
+            sync_hz: None,
+        }
+    }
+
+    /// Sets the external clock frequency.
+    pub fn clock_hz(&mut self, clock_hz: u32) -> &mut Self {
+        self.clock_hz = clock_hz;
+        self
+    }
+
+    /// Sets the sync pulse rate.
+    pub fn sync_hz(&mut self, sync_hz: f32) -> &mut Self {
+        self.sync_hz = Some(sync_hz);
+        self
+    }
+
+    /// Ratio between the clock the device runs on and the internal clock.
+    pub fn clock_ratio(&self) -> f32 {
+        if self.mode.uses_external_clock() {
+            self.clock_hz as f32 / NOMINAL_CLOCK_HZ as f32
+        } else {
+            1.0
+        }
+    }
+
+    /// Rate in Hertz at which the filters run for the given ODR setting.
+    pub fn internal_odr(&self, odr: Odr) -> f32 {
+        odr.odr() * self.clock_ratio()
+    }
+
+    /// Rate in Hertz at which samples are output for the given ODR setting: the sync pulse
+    /// rate with external synchronization, the ODR scaled by the clock otherwise.
+    pub fn effective_odr(&self, odr: Odr) -> f32 {
+        match self.sync_hz {
+            Some(sync_hz) if self.mode.uses_external_sync() => sync_hz,
+            _ => self.internal_odr(odr),
+        }
+    }
+
+    /// Period in microseconds between two output samples for the given ODR setting.
+    pub fn period_us(&self, odr: Odr) -> f32 {
+        1_000_000.0 / self.effective_odr(odr)
+    }
+
+    /// Low-pass filter corner in Hertz for the given ODR setting, scaled by the clock.
+    pub fn effective_lpf(&self, odr: Odr) -> f32 {
+        odr.lpf() * self.clock_ratio()
+    }
+
+    /// High-pass filter corner in Hertz for the given settings, scaled by the clock, `None`
+    /// when the filter is off.
+    pub fn effective_hpf(&self, odr: Odr, hpf: HpfCorner) -> Option<f32> {
+        hpf.hpf_coefficient()
+            .map(|coefficient| coefficient * self.internal_odr(odr))
+    }
+
+    /// Scale a duration computed for the internal clock, such as
+    /// [`crate::Config::settling_time_us`], to the clock the device runs on.
+    pub fn scale_time_us(&self, time_us: u64) -> u64 {
+        if self.mode.uses_external_clock() {
+            time_us * u64::from(NOMINAL_CLOCK_HZ) / u64::from(self.clock_hz.max(1))
+        } else {
+            time_us
+        }
+    }
+
+    /// Check the configuration for the given ODR setting: the external clock must not run the
+    /// device above the fastest ODR, and the sync pulses must not come faster than the ODR.
+    pub fn validate(&self, odr: Odr) -> Result<(), ExternalSyncError> {
+        if self.mode.uses_external_clock()
+            && (self.clock_hz == 0 || self.internal_odr(odr) > Odr::_4000Hz.odr())
+        {
+            return Err(ExternalSyncError::ClockOutOfRange(self.clock_hz));
+        }
+        if let Some(sync_hz) = self.sync_hz.filter(|_| self.mode.uses_external_sync()) {
+            if !(sync_hz > 0.0 && sync_hz <= self.internal_odr(odr)) {
+                return Err(ExternalSyncError::SyncRateOutOfRange(
+                    (sync_hz * 1000.0) as u32,
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for ExternalSync {
+    /// By default, the device runs on its internal clock without external synchronization.
+    fn default() -> Self {
+        ExternalSync::new(ExternalSyncMode::NoExtSync)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn internal_clock_runs_at_the_odr() {
+        let sync = ExternalSync::default();
+        assert_eq!(sync.clock_ratio(), 1.0);
+        assert_eq!(sync.effective_odr(Odr::_1000Hz), 1000.0);
+        assert_eq!(sync.period_us(Odr::_1000Hz), 1000.0);
+        assert_eq!(sync.effective_lpf(Odr::_1000Hz), 250.0);
+        assert_eq!(sync.scale_time_us(1234), 1234);
+        assert_eq!(sync.validate(Odr::_4000Hz), Ok(()));
+
+        // The sync rate is ignored without external synchronization.
+        let mut sync = ExternalSync::new(ExternalSyncMode::ExtClockNoExtSync);
+        sync.sync_hz(10.0);
+        assert_eq!(sync.effective_odr(Odr::_1000Hz), 1000.0);
+        assert_eq!(sync.validate(Odr::_1000Hz), Ok(()));
+    }
+
+    #[test]
+    fn external_clock_scales_the_rates() {
+        let mut sync = ExternalSync::new(ExternalSyncMode::ExtClockNoExtSync);
+        sync.clock_hz(NOMINAL_CLOCK_HZ / 2);
+        assert_eq!(sync.clock_ratio(), 0.5);
+        assert_eq!(sync.effective_odr(Odr::_1000Hz), 500.0);
+        assert_eq!(sync.period_us(Odr::_1000Hz), 2000.0);
+        assert_eq!(sync.effective_lpf(Odr::_1000Hz), 125.0);
+        let hpf = sync.effective_hpf(Odr::_1000Hz, HpfCorner::_24_7).unwrap();
+        assert!((hpf - 1.235).abs() < 1e-4);
+        assert_eq!(sync.effective_hpf(Odr::_1000Hz, HpfCorner::Off), None);
+        assert_eq!(sync.scale_time_us(1000), 2000);
+        assert_eq!(sync.validate(Odr::_4000Hz), Ok(()));
+    }
+
+    #[test]
+    fn external_clock_out_of_range() {
+        let mut sync = ExternalSync::new(ExternalSyncMode::ExtClockNoExtSync);
+        sync.clock_hz(2 * NOMINAL_CLOCK_HZ);
+        assert_eq!(sync.effective_odr(Odr::_2000Hz), 4000.0);
+        assert_eq!(sync.validate(Odr::_2000Hz), Ok(()));
+        assert_eq!(
+            sync.validate(Odr::_4000Hz),
+            Err(ExternalSyncError::ClockOutOfRange(2 * NOMINAL_CLOCK_HZ))
+        );
+
+        sync.clock_hz(0);
+        assert_eq!(
+            sync.validate(Odr::_3_906Hz),
+            Err(ExternalSyncError::ClockOutOfRange(0))
+        );
+        // No division by zero.
+        assert_eq!(sync.scale_time_us(1), u64::from(NOMINAL_CLOCK_HZ));
+    }
+
+    #[test]
+    fn external_sync_outputs_at_the_sync_rate() {
+        let mut sync = ExternalSync::new(ExternalSyncMode::ExtSyncWithInterpolation);
+        assert_eq!(sync.effective_odr(Odr::_1000Hz), 1000.0);
+        sync.sync_hz(800.0);
+        assert_eq!(sync.effective_odr(Odr::_1000Hz), 800.0);
+        assert_eq!(sync.period_us(Odr::_1000Hz), 1250.0);
+        // The filters keep running at the ODR.
+        assert_eq!(sync.effective_lpf(Odr::_1000Hz), 250.0);
+        assert_eq!(sync.validate(Odr::_1000Hz), Ok(()));
+
+        assert_eq!(
+            sync.validate(Odr::_500Hz),
+            Err(ExternalSyncError::SyncRateOutOfRange(800_000))
+        );
+        sync.sync_hz(0.0);
+        assert_eq!(
+            sync.validate(Odr::_500Hz),
+            Err(ExternalSyncError::SyncRateOutOfRange(0))
+        );
+        sync.sync_hz(f32::NAN);
+        assert!(sync.validate(Odr::_500Hz).is_err());
+    }
+}
@@ -0,0 +1,345 @@
+// Copyright © 2023 Analog Devices, Inc. All Rights Reserved. This software is
+// proprietary to Analog Devices, Inc. and its licensors.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+
+use crate::register::Register;
+use crate::{Error, Protocol};
+
+/// Number of transferred bytes kept in a [`TraceEntry`], enough for an acceleration sample.
+pub const TRACE_ENTRY_BYTES: usize = 9;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Direction of a traced transaction.
+pub enum Direction {
+    /// Registers read from the device.
+    Read,
+    /// Registers written to the device.
+    Write,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Register transaction recorded by a [`TracingProtocol`].
+pub struct TraceEntry {
+    /// Register the transaction started at.
+    pub register: Register,
+    /// Direction of the transaction.
+    pub direction: Direction,
+    /// Whether the transaction succeeded.
+    pub succeeded: bool,
+    len: usize,
+    bytes: [u8; TRACE_ENTRY_BYTES],
+}
+
+impl TraceEntry {
+    /// Record a transaction of `data` starting at `register`.
+    /// The bytes of a failed read are not recorded.
+    pub fn new(register: Register, direction: Direction, data: &[u8], succeeded: bool) -> Self {
+        let mut bytes = [0u8; TRACE_ENTRY_BYTES];
+        let kept = data.len().min(TRACE_ENTRY_BYTES);
+        if succeeded || direction == Direction::Write {
+            bytes[..kept].copy_from_slice(&data[..kept]);
+        }
+
+        TraceEntry {
+            register,
+            direction,
+            succeeded,
+            len: data.len(),
+            bytes,
+        }
+    }
+
+    /// Number of bytes transferred, or requested for a failed read.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if no byte was transferred.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// First [`TRACE_ENTRY_BYTES`] bytes transferred, empty for a failed read.
+    pub fn bytes(&self) -> &[u8] {
+        if self.succeeded || self.direction == Direction::Write {
+            &self.bytes[..self.len.min(TRACE_ENTRY_BYTES)]
+        } else {
+            &[]
+        }
+    }
+
+    /// Send the entry to the enabled log outputs, `defmt` and `log`, at trace level.
+    pub fn log(&self) {
+        #[cfg(feature = "defmt")]
+        defmt::trace!("adxl355 {}", self);
+        #[cfg(feature = "log")]
+        log::trace!("adxl355 {}", self);
+    }
+}
+
+impl fmt::Display for TraceEntry {
+    /// Render the entry as `read XDATA3 [9]: 12 34 ... ok`, naming the register.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let direction = match self.direction {
+            Direction::Read => "read",
+            Direction::Write => "write",
+        };
+        write!(f, "{} {:?} [{}]:", direction, self.register, self.len)?;
+        for byte in self.bytes() {
+            write!(f, " {:02X}", byte)?;
+        }
+        if self.bytes().len() < self.len && !self.bytes().is_empty() {
+            write!(f, " ...")?;
+        }
+        if self.succeeded {
+            write!(f, " ok")
+        } else {
+            write!(f, " failed")
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+/// Ring buffer holding the last `N` [`TraceEntry`].
+pub struct TraceBuffer<const N: usize> {
+    entries: [Option<TraceEntry>; N],
+    next: usize,
+    overwritten: u32,
+}
+
+impl<const N: usize> TraceBuffer<N> {
+    /// Create an empty buffer.
+    pub fn new() -> Self {
+        TraceBuffer {
+            entries: [None; N],
+            next: 0,
+            overwritten: 0,
+        }
+    }
+
+    /// Add an entry, overwriting the oldest one when the buffer is full.
+    pub fn push(&mut self, entry: TraceEntry) {
+        if N == 0 {
+            self.overwritten = self.overwritten.saturating_add(1);
+            return;
+        }
+
+        if self.entries[self.next].replace(entry).is_some() {
+            self.overwritten = self.overwritten.saturating_add(1);
+        }
+        self.next = (self.next + 1) % N;
+    }
+
+    /// Iterate over the entries, from the oldest to the newest.
+    pub fn iter(&self) -> impl Iterator<Item = &TraceEntry> {
+        self.entries[self.next..]
+            .iter()
+            .chain(self.entries[..self.next].iter())
+            .flatten()
+    }
+
+    /// Number of entries held.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Check if the buffer holds no entry.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of entries overwritten since the buffer was created or cleared.
+    pub fn overwritten(&self) -> u32 {
+        self.overwritten
+    }
+
+    /// Remove all the entries.
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl<const N: usize> Default for TraceBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// [`Protocol`] wrapper recording every transaction in a [`TraceBuffer`] of the last `N`
+/// entries, and to `defmt` or `log` when these features are enabled.
+pub struct TracingProtocol<P, const N: usize> {
+    protocol: P,
+    buffer: TraceBuffer<N>,
+}
+
+impl<P, const N: usize> TracingProtocol<P, N>
+where
+    P: Protocol,
+{
+    /// Wrap `protocol` with an empty trace buffer.
+    pub fn new(protocol: P) -> Self {
+        TracingProtocol {
+            protocol,
+            buffer: TraceBuffer::new(),
+        }
+    }
+
+    /// Get the recorded transactions.
+    pub fn trace(&self) -> &TraceBuffer<N> {
+        &self.buffer
+    }
+
+    /// Remove the recorded transactions.
+    pub fn clear_trace(&mut self) {
+        self.buffer.clear();
+    }
+
+    /// Release the wrapped protocol.
+    pub fn into_inner(self) -> P {
+        self.protocol
+    }
+
+    /// Record a transaction.
+    fn record(&mut self, register: Register, direction: Direction, data: &[u8], succeeded: bool) {
+        let entry = TraceEntry::new(register, direction, data, succeeded);
+        entry.log();
+        self.buffer.push(entry);
+    }
+}
+
+impl<P, const N: usize> Protocol for TracingProtocol<P, N>
+where
+    P: Protocol,
+{
+    type ProtocolError = P::ProtocolError;
+
+    /// Read a single register.
+    fn read_register(&mut self, register: Register) -> Result<u8, Error<Self::ProtocolError>> {
+        let result = self.protocol.read_register(register);
+        let value = *result.as_ref().unwrap_or(&0);
+        self.record(register, Direction::Read, &[value], result.is_ok());
+        result
+    }
+
+    /// Read `buf.len()` registers from the `start_register` address included.
+    fn read_multiple_registers(
+        &mut self,
+        start_register: Register,
+        buf: &mut [u8],
+    ) -> Result<(), Error<Self::ProtocolError>> {
+        let result = self.protocol.read_multiple_registers(start_register, buf);
+        self.record(start_register, Direction::Read, buf, result.is_ok());
+        result
+    }
+
+    /// Write a single register.
+    fn write_register(
+        &mut self,
+        register: Register,
+        buf: u8,
+    ) -> Result<(), Error<Self::ProtocolError>> {
+        let result = self.protocol.write_register(register, buf);
+        self.record(register, Direction::Write, &[buf], result.is_ok());
+        result
+    }
+
+    /// Write `data.len()` registers from the `start_register` address included.
+    fn write_registers(
+        &mut self,
+        start_register: Register,
+        data: &[u8],
+    ) -> Result<(), Error<Self::ProtocolError>> {
+        let result = self.protocol.write_registers(start_register, data);
+        self.record(start_register, Direction::Write, data, result.is_ok());
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::ToString;
+
+    use super::*;
+
+    fn write(value: u8) -> TraceEntry {
+        TraceEntry::new(Register::RANGE, Direction::Write, &[value], true)
+    }
+
+    fn values<const N: usize>(buffer: &TraceBuffer<N>) -> [Option<u8>; 4] {
+        let mut values = [None; 4];
+        for (value, entry) in values.iter_mut().zip(buffer.iter()) {
+            *value = Some(entry.bytes()[0]);
+        }
+        values
+    }
+
+    #[test]
+    fn trace_buffer_keeps_the_last_entries() {
+        let mut buffer = TraceBuffer::<3>::new();
+        assert!(buffer.is_empty());
+        buffer.push(write(1));
+        buffer.push(write(2));
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(values(&buffer), [Some(1), Some(2), None, None]);
+        assert_eq!(buffer.overwritten(), 0);
+
+        buffer.push(write(3));
+        buffer.push(write(4));
+        buffer.push(write(5));
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(values(&buffer), [Some(3), Some(4), Some(5), None]);
+        assert_eq!(buffer.overwritten(), 2);
+
+        buffer.clear();
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.overwritten(), 0);
+    }
+
+    #[test]
+    fn empty_trace_buffer_counts_every_entry_as_overwritten() {
+        let mut buffer = TraceBuffer::<0>::new();
+        buffer.push(write(1));
+        buffer.push(write(2));
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.overwritten(), 2);
+    }
+
+    #[test]
+    fn trace_entry_keeps_the_first_bytes() {
+        let data = [
+            0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A,
+        ];
+        let entry = TraceEntry::new(Register::FIFO_DATA, Direction::Read, &data, true);
+        assert_eq!(entry.len(), 11);
+        assert_eq!(entry.bytes(), &data[..TRACE_ENTRY_BYTES]);
+        assert_eq!(
+            entry.to_string(),
+            "read FIFO_DATA [11]: 10 11 12 13 14 15 16 17 18 ... ok"
+        );
+
+        let failed = TraceEntry::new(Register::STATUS, Direction::Read, &[0x55], false);
+        assert_eq!(failed.len(), 1);
+        assert!(failed.bytes().is_empty());
+        assert_eq!(failed.to_string(), "read STATUS [1]: failed");
+
+        let failed = TraceEntry::new(Register::RESET, Direction::Write, &[0x52], false);
+        assert_eq!(failed.bytes(), &[0x52]);
+        assert_eq!(failed.to_string(), "write RESET [1]: 52 failed");
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright © 2023 Analog Devices, Inc. All Rights Reserved. This software is
+// proprietary to Analog Devices, Inc. and its licensors.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Physical units of the converted values, enabled with the `units` feature.
+//!
+//! The conversion APIs return the acceleration as an [`Acceleration`] tagged with its unit, so
+//! values in g and in m/s² cannot be mixed up, and the temperature as [`Celsius`].
+
+use micromath::vector::F32x3;
+
+/// Standard gravity in m/s².
+pub const STANDARD_GRAVITY: f32 = 9.806_65;
+
+/// Unit of an [`Acceleration`].
+pub trait AccelerationUnit: Copy + Default {
+    /// Value of 1 g in this unit.
+    const PER_G: f32;
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Acceleration in g, the unit of the device.
+pub struct G;
+
+impl AccelerationUnit for G {
+    const PER_G: f32 = 1.0;
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// Acceleration in m/s², with the [`STANDARD_GRAVITY`].
+pub struct MetersPerSecondSquared;
+
+impl AccelerationUnit for MetersPerSecondSquared {
+    const PER_G: f32 = STANDARD_GRAVITY;
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Acceleration on the three axes in the unit `U`.
+pub struct Acceleration<U: AccelerationUnit> {
+    /// Acceleration on the x-axis.
+    pub x: f32,
+    /// Acceleration on the y-axis.
+    pub y: f32,
+    /// Acceleration on the z-axis.
+    pub z: f32,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    unit: U,
+}
+
+impl<U: AccelerationUnit> Acceleration<U> {
+    /// Create an acceleration from values in the unit `U`.
+    pub fn new(x: f32, y: f32, z: f32) -> Self {
+        Acceleration {
+            x,
+            y,
+            z,
+            unit: U::default(),
+        }
+    }
+
+    /// Create an acceleration from values in g, as returned by the untyped APIs.
+    pub fn from_g(g: F32x3) -> Self {
+        Acceleration::new(g.x * U::PER_G, g.y * U::PER_G, g.z * U::PER_G)
+    }
+
+    /// Convert to the unit `V`.
+    pub fn convert<V: AccelerationUnit>(self) -> Acceleration<V> {
+        let factor = V::PER_G / U::PER_G;
+        Acceleration::new(self.x * factor, self.y * factor, self.z * factor)
+    }
+
+    /// Values in the unit `U`, without the unit.
+    pub fn to_vector(self) -> F32x3 {
+        F32x3 {
+            x: self.x,
+            y: self.y,
+            z: self.z,
+        }
+    }
+}
+
+impl From<Acceleration<G>> for Acceleration<MetersPerSecondSquared> {
+    fn from(acceleration: Acceleration<G>) -> Self {
+        acceleration.convert()
+    }
+}
+
+impl From<Acceleration<MetersPerSecondSquared>> for Acceleration<G> {
+    fn from(acceleration: Acceleration<MetersPerSecondSquared>) -> Self {
+        acceleration.convert()
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Temperature in degrees Celsius.
+pub struct Celsius(pub f32);
@@ -0,0 +1,87 @@
+// Copyright © 2023 Analog Devices, Inc. All Rights Reserved. This software is
+// proprietary to Analog Devices, Inc. and its licensors.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::config::Range;
+use crate::regs;
+
+/// Member of the ADXL35x family sharing the ADXL355 register map.
+///
+/// The variants only differ by their part ID and their set of ranges, the FIFO, activity,
+/// synchronization and temperature features are common to all of them.
+/// The ADXL354 has analog outputs only and therefore is not supported.
+///
+/// The ADXL355 and the ADXL357 report the same part ID, 0xED, so the identity check at
+/// construction cannot tell them apart: an ADXL357 opened as an [`Adxl355Variant`] is accepted
+/// and its ranges are decoded as the ADXL355 ones, scaling the accelerations 5 times too small.
+/// Always select the variant matching the part fitted on the board.
+pub trait Variant {
+    /// Expected value of the [`crate::register::Register::PARTID`] register.
+    const PART_ID: u8;
+
+    /// Ranges selected by the values `0b01`, `0b10` and `0b11` of the range bits of the
+    /// [`crate::register::Register::RANGE`] register.
+    const RANGES: [Range; 3];
+
+    /// Get the [`Range`] from the [`crate::register::Register::RANGE`] register value,
+    /// `None` for the reserved value `0b00`.
+    fn range_from_register(value: u8) -> Option<Range> {
+        match value & regs::Range::RANGE_BITS {
+            1 => Some(Self::RANGES[0]),
+            2 => Some(Self::RANGES[1]),
+            3 => Some(Self::RANGES[2]),
+            _ => None,
+        }
+    }
+
+    /// Check if the [`Range`] is available on this variant.
+    fn supports(range: Range) -> bool {
+        Self::RANGES.contains(&range)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// ADXL355, ±2 g, ±4 g and ±8 g ranges.
+///
+/// Shares its part ID with the [`Adxl357Variant`], an ADXL357 opened with this variant is not
+/// detected, see [`Variant`].
+pub struct Adxl355Variant;
+
+impl Variant for Adxl355Variant {
+    const PART_ID: u8 = 0xED;
+    const RANGES: [Range; 3] = [Range::_2G, Range::_4G, Range::_8G];
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// ADXL357, ±10.24 g, ±20.48 g and ±40.96 g ranges.
+///
+/// Shares its part ID with the [`Adxl355Variant`], an ADXL355 opened with this variant is not
+/// detected, see [`Variant`].
+pub struct Adxl357Variant;
+
+impl Variant for Adxl357Variant {
+    const PART_ID: u8 = 0xED;
+    const RANGES: [Range; 3] = [Range::_10G, Range::_20G, Range::_40G];
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// ADXL359, ±10.24 g, ±20.48 g and ±40.96 g ranges.
+pub struct Adxl359Variant;
+
+impl Variant for Adxl359Variant {
+    const PART_ID: u8 = 0xE9;
+    const RANGES: [Range; 3] = [Range::_10G, Range::_20G, Range::_40G];
+}